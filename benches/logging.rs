@@ -0,0 +1,140 @@
+use criterion::{
+    black_box, criterion_group, criterion_main,
+    measurement::{Measurement, ValueFormatter},
+    Criterion, Throughput,
+};
+use datadog_logs::{
+    client::DataDogClient,
+    error::DataDogLoggerError,
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger, DataDogLoggerBuilder},
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Counts allocations made by current thread, so that logger thread does not affect results
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Measures number of allocations instead of time, so that criterion reports and compares them
+struct Allocations;
+
+impl Measurement for Allocations {
+    type Intermediate = usize;
+    type Value = usize;
+
+    fn start(&self) -> Self::Intermediate {
+        ALLOCATIONS.with(Cell::get)
+    }
+
+    fn end(&self, start: Self::Intermediate) -> Self::Value {
+        ALLOCATIONS.with(Cell::get) - start
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &AllocationsFormatter
+    }
+}
+
+struct AllocationsFormatter;
+
+impl ValueFormatter for AllocationsFormatter {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let (count, unit) = match *throughput {
+            Throughput::Bytes(bytes) | Throughput::BytesDecimal(bytes) => (bytes, "allocs/B"),
+            Throughput::Elements(elements) => (elements, "allocs/elem"),
+        };
+        for value in values {
+            *value /= count as f64;
+        }
+        unit
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+}
+
+struct NoopClient;
+
+impl DataDogClient for NoopClient {
+    fn send(&mut self, _messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        Ok(())
+    }
+}
+
+fn builder() -> DataDogLoggerBuilder {
+    DataDogLogger::builder()
+        .service("checkout-service")
+        .hostname("ip-10-0-12-34.eu-west-1.compute.internal")
+        .env("production")
+        .version("1.4.2")
+        .tags("team:payments,region:eu-west-1".parse().unwrap())
+        .channel_capacity(None)
+}
+
+fn bench_log<M: Measurement>(c: &mut Criterion<M>, measured: &str) {
+    let logger = builder().client(NoopClient).build_blocking().unwrap();
+
+    let mut group = c.benchmark_group(measured);
+    group.bench_function("DataDogLogger::log", |b| {
+        b.iter(|| logger.log(black_box("Order placed"), DataDogLogLevel::Informational))
+    });
+    group.finish();
+}
+
+fn bench_latency(c: &mut Criterion) {
+    bench_log(c, "latency");
+}
+
+fn bench_allocations(c: &mut Criterion<Allocations>) {
+    bench_log(c, "allocations");
+}
+
+criterion_group!(latency, bench_latency);
+// number of allocations is usually the same for every sample, which plots cannot show
+criterion_group! {
+    name = allocations;
+    config = Criterion::default().with_measurement(Allocations).without_plots();
+    targets = bench_allocations
+}
+criterion_main!(latency, allocations);
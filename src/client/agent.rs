@@ -0,0 +1,271 @@
+#[cfg(feature = "nonblocking-core")]
+use super::AsyncDataDogClient;
+use super::DataDogClient;
+use crate::config::DataDogConfig;
+use crate::error::DataDogLoggerError;
+use crate::logger::DataDogLog;
+#[cfg(feature = "nonblocking-core")]
+use async_trait::async_trait;
+use std::io::{self, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+#[cfg(unix)]
+use std::os::unix::net::{UnixDatagram, UnixStream};
+#[cfg(unix)]
+use std::path::PathBuf;
+
+/// Datadog client sending logs to a local DataDog agent listening on UDP, TCP or Unix socket
+///
+/// Logs are sent as newline delimited JSON without API key, which is only known to the agent.
+/// Listener is configured with [`DataDogAgentConfig`](crate::config::DataDogAgentConfig).
+///
+/// Connection is established lazily when first batch of logs is sent and reestablished after every failure.
+/// When sending datagrams fails partway, retry of the same batch sends only datagrams that were not sent yet.
+/// Nonblocking logger sends logs in its task as well, since the agent runs on the same host.
+#[derive(Debug)]
+pub struct AgentDataDogClient {
+    target: AgentTarget,
+    max_datagram_size: usize,
+    connection: Option<AgentConnection>,
+    // datagrams of the last batch that failed and number of them that were sent
+    unsent: Option<(Vec<Vec<u8>>, usize)>,
+    notices: Vec<String>,
+}
+
+/// Largest payload of UDP datagram over IPv4
+const MAX_UDP_PAYLOAD: usize = 65_507;
+const TRUNCATION_MARKER: &str = "...";
+
+#[derive(Debug)]
+enum AgentTarget {
+    Udp(String),
+    Tcp(String),
+    #[cfg(unix)]
+    UnixStream(PathBuf),
+    #[cfg(unix)]
+    UnixDatagram(PathBuf),
+}
+
+#[derive(Debug)]
+enum AgentConnection {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    UnixStream(UnixStream),
+    #[cfg(unix)]
+    UnixDatagram(UnixDatagram),
+}
+
+impl AgentDataDogClient {
+    /// Creates new DataDog agent client
+    pub fn new(config: &DataDogConfig) -> Result<Self, DataDogLoggerError> {
+        let agent_config = &config.agent_config;
+        let (scheme, address) = agent_config.url.split_once("://").ok_or_else(|| {
+            DataDogLoggerError::ConfigError(format!("Invalid agent URL : {}", agent_config.url))
+        })?;
+
+        let target = match scheme {
+            "udp" => AgentTarget::Udp(address.into()),
+            "tcp" => AgentTarget::Tcp(address.into()),
+            #[cfg(unix)]
+            "unix" => AgentTarget::UnixStream(address.into()),
+            #[cfg(unix)]
+            "unixgram" => AgentTarget::UnixDatagram(address.into()),
+            _ => {
+                return Err(DataDogLoggerError::ConfigError(format!(
+                    "Unsupported agent protocol : {}",
+                    scheme
+                )))
+            }
+        };
+
+        Ok(AgentDataDogClient {
+            target,
+            max_datagram_size: agent_config.max_datagram_size,
+            connection: None,
+            unsent: None,
+            notices: Vec::new(),
+        })
+    }
+
+    /// Returns maximum size of datagram if logs are sent in datagrams
+    fn datagram_limit(&self) -> Option<usize> {
+        match self.target {
+            AgentTarget::Udp(_) => Some(self.max_datagram_size.min(MAX_UDP_PAYLOAD)),
+            #[cfg(unix)]
+            AgentTarget::UnixDatagram(_) => Some(self.max_datagram_size),
+            _ => None,
+        }
+    }
+
+    /// Serializes log as a line not exceeding maximum size, truncating its message if needed
+    fn line(
+        &mut self,
+        log: &DataDogLog,
+        max_size: usize,
+    ) -> Result<Option<Vec<u8>>, DataDogLoggerError> {
+        let mut line = serialize(log)?;
+        if line.len() <= max_size {
+            return Ok(Some(line));
+        }
+
+        let original = line.len();
+        let mut truncated = log.clone();
+        while line.len() > max_size && !truncated.message.is_empty() {
+            let mut length = truncated
+                .message
+                .len()
+                .saturating_sub(line.len() - max_size + TRUNCATION_MARKER.len());
+            while !truncated.message.is_char_boundary(length) {
+                length -= 1;
+            }
+            truncated.message.truncate(length);
+            if !truncated.message.is_empty() {
+                truncated.message.push_str(TRUNCATION_MARKER);
+            }
+            line = serialize(&truncated)?;
+        }
+
+        if line.len() <= max_size {
+            self.notices.push(format!(
+                "Truncating message of log of {} bytes exceeding maximum datagram size of {} bytes",
+                original, max_size
+            ));
+            Ok(Some(line))
+        } else {
+            self.notices.push(format!(
+                "Dropping log of {} bytes exceeding maximum datagram size of {} bytes",
+                original, max_size
+            ));
+            Ok(None)
+        }
+    }
+
+    fn connect(&self) -> Result<AgentConnection, DataDogLoggerError> {
+        Ok(match self.target {
+            AgentTarget::Udp(ref address) => {
+                let address = resolve(address)?;
+                let local: SocketAddr = if address.is_ipv4() {
+                    ([0, 0, 0, 0], 0).into()
+                } else {
+                    ([0u16; 8], 0).into()
+                };
+                let socket = UdpSocket::bind(local)?;
+                socket.connect(address)?;
+                AgentConnection::Udp(socket)
+            }
+            AgentTarget::Tcp(ref address) => {
+                AgentConnection::Tcp(TcpStream::connect(resolve(address)?)?)
+            }
+            #[cfg(unix)]
+            AgentTarget::UnixStream(ref path) => {
+                AgentConnection::UnixStream(UnixStream::connect(path)?)
+            }
+            #[cfg(unix)]
+            AgentTarget::UnixDatagram(ref path) => {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(path)?;
+                AgentConnection::UnixDatagram(socket)
+            }
+        })
+    }
+}
+
+fn resolve(address: &str) -> Result<SocketAddr, DataDogLoggerError> {
+    address.to_socket_addrs()?.next().ok_or_else(|| {
+        DataDogLoggerError::ConfigError(format!("Cannot resolve agent address : {}", address))
+    })
+}
+
+fn serialize(log: &DataDogLog) -> Result<Vec<u8>, DataDogLoggerError> {
+    let mut line = serde_json::to_vec(log)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// Groups lines into datagrams not exceeding maximum size
+fn datagrams(lines: &[Vec<u8>], max_size: usize) -> Vec<Vec<u8>> {
+    let mut datagrams: Vec<Vec<u8>> = Vec::new();
+    for line in lines {
+        match datagrams.last_mut() {
+            Some(datagram) if datagram.len() + line.len() <= max_size => {
+                datagram.extend_from_slice(line)
+            }
+            _ => datagrams.push(line.clone()),
+        }
+    }
+    datagrams
+}
+
+impl AgentConnection {
+    /// Sends data as a single datagram or writes it to the stream
+    fn send(&mut self, data: &[u8]) -> io::Result<()> {
+        match self {
+            AgentConnection::Udp(socket) => socket.send(data).map(|_| ()),
+            #[cfg(unix)]
+            AgentConnection::UnixDatagram(socket) => socket.send(data).map(|_| ()),
+            AgentConnection::Tcp(stream) => write_all(stream, data),
+            #[cfg(unix)]
+            AgentConnection::UnixStream(stream) => write_all(stream, data),
+        }
+    }
+}
+
+fn write_all<W: Write>(stream: &mut W, data: &[u8]) -> io::Result<()> {
+    stream.write_all(data)?;
+    stream.flush()
+}
+
+impl DataDogClient for AgentDataDogClient {
+    fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        let datagram_limit = self.datagram_limit();
+        let mut lines = Vec::with_capacity(messages.len());
+        for message in messages {
+            match datagram_limit {
+                Some(limit) => lines.extend(self.line(message, limit)?),
+                None => lines.push(serialize(message)?),
+            }
+        }
+
+        // streams get all lines at once, as if in a single datagram
+        let chunks = match datagram_limit {
+            Some(limit) => datagrams(&lines, limit),
+            None => vec![lines.concat()],
+        };
+        let sent = match self.unsent.take() {
+            Some((unsent, sent)) if unsent == chunks => sent,
+            _ => 0,
+        };
+
+        let connection = match self.connection {
+            Some(ref mut connection) => connection,
+            None => self.connection.insert(self.connect()?),
+        };
+
+        for (index, chunk) in chunks.iter().enumerate().skip(sent) {
+            if let Err(e) = connection.send(chunk) {
+                self.connection = None;
+                if datagram_limit.is_some() {
+                    self.unsent = Some((chunks, index));
+                }
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.notices)
+    }
+}
+
+#[cfg(feature = "nonblocking-core")]
+#[async_trait]
+impl AsyncDataDogClient for AgentDataDogClient {
+    async fn send_async(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        self.send(messages)
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.notices)
+    }
+}
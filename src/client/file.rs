@@ -0,0 +1,246 @@
+#[cfg(feature = "nonblocking-core")]
+use super::AsyncDataDogClient;
+use super::DataDogClient;
+use crate::config::{DataDogConfig, DataDogFileConfig, DataDogFsyncPolicy};
+use crate::error::DataDogLoggerError;
+use crate::logger::DataDogLog;
+#[cfg(feature = "nonblocking-core")]
+use async_trait::async_trait;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+/// Datadog client writing logs to a file as JSON lines, e.g. to be tailed by DataDog agent
+///
+/// File is rotated by size or time as configured in [`DataDogFileConfig`](crate::config::DataDogFileConfig).
+/// When the file is moved or removed by an external tool, e.g. `logrotate`, it is reopened before next batch is written.
+/// Reopening can also be requested explicitly with [`FileReopenHandle`], e.g. on `SIGHUP`.
+/// Failure to sync a completely written batch is reported to self log instead of failing the batch,
+/// so that its logs are not written again. A batch failing partway is truncated from the file before it is retried.
+///
+/// Nonblocking logger writes the file in its task as well, since writes of a batch are short.
+#[derive(Debug)]
+pub struct FileDataDogClient {
+    path: PathBuf,
+    max_size: Option<u64>,
+    rotation_interval: Option<Duration>,
+    max_files: usize,
+    fsync: DataDogFsyncPolicy,
+    file: Option<OpenFile>,
+    last_sync: Instant,
+    reopen: Arc<AtomicBool>,
+    notices: Vec<String>,
+}
+
+#[derive(Debug)]
+struct OpenFile {
+    file: File,
+    size: u64,
+    opened: Instant,
+    #[cfg(unix)]
+    inode: u64,
+}
+
+/// Requests [`FileDataDogClient`] to reopen its file before writing next batch
+///
+/// # Examples
+///```rust
+///use datadog_logs::{client::FileDataDogClient, config::DataDogConfig};
+///
+///let client = FileDataDogClient::new(&DataDogConfig::default()).unwrap();
+///let handle = client.reopen_handle();
+///
+/// // e.g. in SIGHUP handler registered with `signal-hook`
+///handle.reopen();
+///```
+#[derive(Debug, Clone)]
+pub struct FileReopenHandle(Arc<AtomicBool>);
+
+impl FileReopenHandle {
+    /// Requests reopening of the file
+    pub fn reopen(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl FileDataDogClient {
+    /// Creates new DataDog file client. File is opened when first batch of logs is written.
+    pub fn new(config: &DataDogConfig) -> Result<Self, DataDogLoggerError> {
+        let DataDogFileConfig {
+            path,
+            max_size_bytes,
+            rotation_interval_ms,
+            max_files,
+            fsync,
+        } = config.file_config.clone();
+
+        if path.is_empty() {
+            return Err(DataDogLoggerError::ConfigError(
+                "Path of log file is empty".into(),
+            ));
+        }
+
+        Ok(FileDataDogClient {
+            path: path.into(),
+            max_size: max_size_bytes,
+            rotation_interval: rotation_interval_ms.map(Duration::from_millis),
+            max_files,
+            fsync,
+            file: None,
+            last_sync: Instant::now(),
+            reopen: Arc::new(AtomicBool::new(false)),
+            notices: Vec::new(),
+        })
+    }
+
+    /// Returns handle requesting reopening of the file
+    pub fn reopen_handle(&self) -> FileReopenHandle {
+        FileReopenHandle(self.reopen.clone())
+    }
+
+    fn open(&self) -> Result<OpenFile, DataDogLoggerError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let metadata = file.metadata()?;
+        Ok(OpenFile {
+            file,
+            size: metadata.len(),
+            opened: Instant::now(),
+            #[cfg(unix)]
+            inode: std::os::unix::fs::MetadataExt::ino(&metadata),
+        })
+    }
+
+    /// Checks whether file was moved or removed since it was opened
+    fn replaced(&self, file: &OpenFile) -> bool {
+        match fs::metadata(&self.path) {
+            #[cfg(unix)]
+            Ok(metadata) => std::os::unix::fs::MetadataExt::ino(&metadata) != file.inode,
+            #[cfg(not(unix))]
+            Ok(_) => false,
+            Err(_) => true,
+        }
+    }
+
+    fn needs_rotation(&self, file: &OpenFile, incoming: u64) -> bool {
+        let too_big = self
+            .max_size
+            .is_some_and(|max| file.size > 0 && file.size + incoming > max);
+        let too_old = self
+            .rotation_interval
+            .is_some_and(|interval| file.opened.elapsed() >= interval);
+        too_big || too_old
+    }
+
+    /// Shifts rotated files by one, dropping the oldest, and moves current file in place of the newest
+    fn rotate(&self) -> Result<(), DataDogLoggerError> {
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+            return Ok(());
+        }
+        let oldest = rotated_path(&self.path, self.max_files);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for index in (1..self.max_files).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        Ok(())
+    }
+
+    fn sync(&mut self, file: &OpenFile) {
+        let sync = match self.fsync {
+            DataDogFsyncPolicy::Never => false,
+            DataDogFsyncPolicy::EveryBatch => true,
+            DataDogFsyncPolicy::Interval(ms) => {
+                self.last_sync.elapsed() >= Duration::from_millis(ms)
+            }
+        };
+        if sync {
+            if let Err(e) = file.file.sync_data() {
+                self.notices
+                    .push(format!("Unable to sync {} : {}", self.path.display(), e));
+            }
+            self.last_sync = Instant::now();
+        }
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", index));
+    rotated.into()
+}
+
+impl DataDogClient for FileDataDogClient {
+    fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        let mut lines = Vec::new();
+        for message in messages {
+            serde_json::to_writer(&mut lines, message)?;
+            lines.push(b'\n');
+        }
+        let incoming = lines.len() as u64;
+
+        let mut file = match self.file.take() {
+            Some(file) if self.reopen.swap(false, Ordering::Relaxed) || self.replaced(&file) => {
+                self.open()?
+            }
+            Some(file) if self.needs_rotation(&file, incoming) => {
+                // file has to be closed before it is renamed on some platforms
+                std::mem::drop(file);
+                self.rotate()?;
+                self.open()?
+            }
+            Some(file) => file,
+            None => {
+                self.reopen.store(false, Ordering::Relaxed);
+                self.open()?
+            }
+        };
+
+        if let Err(e) = file.file.write_all(&lines) {
+            // partially written batch is removed, so that retry does not duplicate its logs or leave a torn line,
+            // otherwise file is reopened with next batch
+            match file.file.set_len(file.size) {
+                Ok(()) => self.file = Some(file),
+                Err(e) => self.notices.push(format!(
+                    "Unable to remove partially written batch from {} : {}",
+                    self.path.display(),
+                    e
+                )),
+            }
+            return Err(e.into());
+        }
+        file.size += incoming;
+        self.sync(&file);
+        self.file = Some(file);
+        Ok(())
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.notices)
+    }
+}
+
+#[cfg(feature = "nonblocking-core")]
+#[async_trait]
+impl AsyncDataDogClient for FileDataDogClient {
+    async fn send_async(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        self.send(messages)
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.notices)
+    }
+}
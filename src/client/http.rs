@@ -1,76 +1,310 @@
-#[cfg(feature = "nonblocking")]
-use super::AsyncDataDogClient;
-use super::DataDogClient;
-use crate::config::DataDogConfig;
+#[cfg(feature = "nonblocking-core")]
+use super::{transport::AsyncHttpTransport, AsyncDataDogClient};
+use super::{
+    transport::{DefaultHttpTransport, HttpRequest, HttpResponse, HttpTransport},
+    DataDogClient,
+};
+use crate::config::{ApiKeySource, DataDogConfig, DataDogHeaderHook};
 use crate::error::DataDogLoggerError;
 use crate::logger::DataDogLog;
-#[cfg(feature = "nonblocking")]
+#[cfg(feature = "nonblocking-core")]
 use async_trait::async_trait;
-#[cfg(feature = "nonblocking")]
-use reqwest;
+use std::mem;
+use std::time::{Duration, Instant};
 use url::Url;
 
+const DEFAULT_USER_AGENT: &str = concat!("datadog-logs/", env!("CARGO_PKG_VERSION"));
+
 /// Datadog network client using HTTP protocol
+///
+/// Logs are sent to `url` of [`DataDogHttpConfig`](crate::config::DataDogHttpConfig).
+/// After `failover_threshold` consecutive failures client switches to the next of `fallback_urls`.
+/// While a fallback url is used, primary url is probed with logs every `failback_probe_interval_ms`
+/// and client fails back to it once it succeeds. Switches are reported to self log.
+///
+/// Requests are built and their responses classified by the client, while they are delivered by a transport,
+/// [`DefaultHttpTransport`](crate::client::DefaultHttpTransport) unless another is given with
+/// [`with_transport`](HttpDataDogClient::with_transport).
+/// Besides `Content-Type` and `DD-API-KEY`, requests carry `User-Agent` identifying this crate,
+/// `headers` of the configuration and headers returned by its `header_hook`.
 #[derive(Debug)]
-pub struct HttpDataDogClient {
-    datadog_url: Url,
-    api_key: String,
+pub struct HttpDataDogClient<T = DefaultHttpTransport> {
+    endpoints: Endpoints,
+    api_key: ApiKeySource,
+    headers: Vec<(String, String)>,
+    header_hook: Option<DataDogHeaderHook>,
+    transport: T,
 }
 
 impl HttpDataDogClient {
     /// Creates new DataDog HTTP(S) logger
     pub fn new(config: &DataDogConfig) -> Result<Self, DataDogLoggerError> {
-        let http_config = config.http_config.clone();
+        let transport = DefaultHttpTransport::new(&config.http_config)?;
+        HttpDataDogClient::with_transport(config, transport)
+    }
+
+    /// Creates client for nonblocking logger on runtime other than tokio, see [`DefaultHttpTransport::unblocking`]
+    #[cfg(any(feature = "with-async-std", feature = "with-smol"))]
+    pub(crate) fn unblocking(config: &DataDogConfig) -> Result<Self, DataDogLoggerError> {
+        let transport = DefaultHttpTransport::unblocking(&config.http_config)?;
+        HttpDataDogClient::with_transport(config, transport)
+    }
+}
+
+impl<T> HttpDataDogClient<T> {
+    /// Creates new DataDog HTTP(S) logger sending requests with given transport
+    pub fn with_transport(
+        config: &DataDogConfig,
+        transport: T,
+    ) -> Result<Self, DataDogLoggerError> {
+        let http_config = &config.http_config;
+
+        let mut urls = vec![Url::parse(&http_config.url)?];
+        for url in http_config.fallback_urls.iter() {
+            urls.push(Url::parse(url)?);
+        }
+
+        let mut headers = vec![("User-Agent".to_string(), DEFAULT_USER_AGENT.to_string())];
+        for (name, value) in http_config.headers.iter() {
+            validate_header(name, value)?;
+            set_header(&mut headers, name, value);
+        }
 
         Ok(HttpDataDogClient {
-            api_key: config.apikey.clone().into(),
-            datadog_url: Url::parse(&http_config.url)?,
+            api_key: config.apikey.clone(),
+            headers,
+            header_hook: http_config.header_hook.clone(),
+            transport,
+            endpoints: Endpoints {
+                urls,
+                active: 0,
+                failures: 0,
+                threshold: http_config.failover_threshold.max(1),
+                probe_interval: Duration::from_millis(http_config.failback_probe_interval_ms),
+                last_probe: Instant::now(),
+                notices: Vec::new(),
+            },
         })
     }
+
+    fn request(
+        &self,
+        url: &Url,
+        messages: &[DataDogLog],
+    ) -> Result<HttpRequest, DataDogLoggerError> {
+        let mut request = HttpRequest {
+            url: url.clone(),
+            headers: vec![
+                ("Content-Type".into(), "application/json".into()),
+                ("DD-API-KEY".into(), self.api_key.get()?),
+            ],
+            body: serde_json::to_vec(messages)?,
+        };
+        for (name, value) in self.headers.iter() {
+            set_header(&mut request.headers, name, value);
+        }
+        if let Some(ref hook) = self.header_hook {
+            for (name, value) in hook.headers(&request) {
+                validate_header(&name, &value)?;
+                set_header(&mut request.headers, &name, &value);
+            }
+        }
+        Ok(request)
+    }
+}
+
+/// Replaces header of the same name or adds a new one
+fn set_header(headers: &mut Vec<(String, String)>, name: &str, value: &str) {
+    match headers
+        .iter_mut()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+    {
+        Some(header) => *header = (name.to_string(), value.to_string()),
+        None => headers.push((name.to_string(), value.to_string())),
+    }
+}
+
+fn validate_header(name: &str, value: &str) -> Result<(), DataDogLoggerError> {
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c));
+    let valid_value = !value.chars().any(|c| c.is_control() && c != '\t');
+    if valid_name && valid_value {
+        Ok(())
+    } else {
+        Err(DataDogLoggerError::ConfigError(format!(
+            "Invalid header : {}",
+            name
+        )))
+    }
+}
+
+/// Error of a request along with whether it counts as a failure of the endpoint
+#[derive(Debug)]
+struct Failure {
+    error: DataDogLoggerError,
+    endpoint: bool,
+}
+
+impl Failure {
+    /// Failure caused by the request itself, e.g. unavailable API key, that other endpoints would not fix
+    fn request(error: DataDogLoggerError) -> Self {
+        Failure {
+            error,
+            endpoint: false,
+        }
+    }
+
+    /// Failure to deliver the request to the endpoint
+    fn endpoint(error: DataDogLoggerError) -> Self {
+        Failure {
+            error,
+            endpoint: true,
+        }
+    }
+}
+
+/// Only server errors and throttling count as endpoint failures,
+/// other statuses, e.g. 400, 403 or 413, are caused by the request itself.
+fn classify(response: HttpResponse) -> Result<(), Failure> {
+    if (200..300).contains(&response.status) {
+        return Ok(());
+    }
+    let error = DataDogLoggerError::OtherError(format!(
+        "Datadog response does not indicate success. Status code : {}, Body : {}",
+        response.status, response.body
+    ));
+    if response.status >= 500 || response.status == 429 {
+        Err(Failure::endpoint(error))
+    } else {
+        Err(Failure::request(error))
+    }
+}
+
+impl<T: HttpTransport> HttpDataDogClient<T> {
+    fn post(&mut self, url: &Url, messages: &[DataDogLog]) -> Result<(), Failure> {
+        let request = self.request(url, messages).map_err(Failure::request)?;
+        classify(self.transport.send(&request).map_err(Failure::endpoint)?)
+    }
 }
 
-impl DataDogClient for HttpDataDogClient {
+impl<T: HttpTransport> DataDogClient for HttpDataDogClient<T> {
     fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
-        let formatted_message = serde_json::to_string(&messages)?;
-        let result = attohttpc::post(&self.datadog_url)
-            .header_append("Content-Type", "application/json")
-            .header_append("DD-API-KEY", &self.api_key)
-            .text(formatted_message)
-            .send()?;
-
-        if !result.is_success() {
-            Err(DataDogLoggerError::OtherError(format!(
-                "Datadog response does not indicate success. Status code : {}, Body : {}",
-                result.status(),
-                result.text().unwrap_or_default()
-            )))
-        } else {
-            Ok(())
+        if self.endpoints.probe_due() {
+            let primary = self.endpoints.primary().clone();
+            let ok = self.post(&primary, messages).is_ok();
+            self.endpoints.probed(ok);
+            if ok {
+                return Ok(());
+            }
         }
+
+        let current = self.endpoints.current().clone();
+        let result = self.post(&current, messages);
+        self.endpoints.report(&result);
+        result.map_err(|failure| failure.error)
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        mem::take(&mut self.endpoints.notices)
     }
 }
 
-#[cfg(feature = "nonblocking")]
+#[cfg(feature = "nonblocking-core")]
+impl<T: AsyncHttpTransport> HttpDataDogClient<T> {
+    async fn post_async(&mut self, url: &Url, messages: &[DataDogLog]) -> Result<(), Failure> {
+        let request = self.request(url, messages).map_err(Failure::request)?;
+        classify(
+            self.transport
+                .send_async(&request)
+                .await
+                .map_err(Failure::endpoint)?,
+        )
+    }
+}
+
+#[cfg(feature = "nonblocking-core")]
 #[async_trait]
-impl AsyncDataDogClient for HttpDataDogClient {
+impl<T: AsyncHttpTransport> AsyncDataDogClient for HttpDataDogClient<T> {
     async fn send_async(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
-        let client = reqwest::Client::new();
-        let response = client
-            .post(self.datadog_url.clone())
-            .header("Content-Type", "application/json")
-            .header("DD-API-KEY", &self.api_key)
-            .json(messages)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            Err(DataDogLoggerError::OtherError(format!(
-                "Datadog response does not indicate success. Status code : {}, Body : {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            )))
-        } else {
-            Ok(())
+        if self.endpoints.probe_due() {
+            let primary = self.endpoints.primary().clone();
+            let ok = self.post_async(&primary, messages).await.is_ok();
+            self.endpoints.probed(ok);
+            if ok {
+                return Ok(());
+            }
+        }
+
+        let current = self.endpoints.current().clone();
+        let result = self.post_async(&current, messages).await;
+        self.endpoints.report(&result);
+        result.map_err(|failure| failure.error)
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        mem::take(&mut self.endpoints.notices)
+    }
+}
+
+/// Ordered intake urls with health of the one in use
+#[derive(Debug)]
+struct Endpoints {
+    urls: Vec<Url>,
+    active: usize,
+    failures: u32,
+    threshold: u32,
+    probe_interval: Duration,
+    last_probe: Instant,
+    notices: Vec<String>,
+}
+
+impl Endpoints {
+    fn primary(&self) -> &Url {
+        &self.urls[0]
+    }
+
+    fn current(&self) -> &Url {
+        &self.urls[self.active]
+    }
+
+    fn probe_due(&self) -> bool {
+        self.active != 0 && self.last_probe.elapsed() >= self.probe_interval
+    }
+
+    fn probed(&mut self, ok: bool) {
+        self.last_probe = Instant::now();
+        if ok {
+            self.notices
+                .push(format!("Failing back to {}", self.primary()));
+            self.active = 0;
+            self.failures = 0;
+        }
+    }
+
+    fn report(&mut self, result: &Result<(), Failure>) {
+        match result {
+            Ok(()) => {
+                self.failures = 0;
+                return;
+            }
+            Err(failure) if !failure.endpoint => return,
+            Err(_) => {}
+        }
+
+        self.failures += 1;
+        if self.failures >= self.threshold && self.urls.len() > 1 {
+            let next = (self.active + 1) % self.urls.len();
+            self.notices.push(format!(
+                "Switching from {} to {} after {} consecutive failures",
+                self.current(),
+                self.urls[next],
+                self.failures
+            ));
+            self.active = next;
+            self.failures = 0;
+            self.last_probe = Instant::now();
         }
     }
 }
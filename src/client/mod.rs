@@ -1,22 +1,80 @@
+mod agent;
+mod file;
 mod http;
+mod multi;
+mod syslog;
+mod tcp;
+mod tls;
+mod transport;
 
+pub use agent::AgentDataDogClient;
+pub use file::{FileDataDogClient, FileReopenHandle};
 pub use http::HttpDataDogClient;
+pub use multi::{Destination, MultiDataDogClient};
+pub use syslog::SyslogDataDogClient;
+pub use tcp::TcpDataDogClient;
+#[cfg(feature = "nonblocking-core")]
+pub use transport::AsyncHttpTransport;
+#[cfg(feature = "attohttpc")]
+pub use transport::AttohttpcTransport;
+#[cfg(feature = "hyper")]
+pub use transport::HyperTransport;
+#[cfg(feature = "reqwest")]
+pub use transport::ReqwestTransport;
+#[cfg(feature = "blocking")]
+pub use transport::UnblockHttpTransport;
+#[cfg(feature = "ureq")]
+pub use transport::UreqTransport;
+pub use transport::{DefaultHttpTransport, HttpRequest, HttpResponse, HttpTransport};
 
 use crate::error::DataDogLoggerError;
 use crate::logger::DataDogLog;
-#[cfg(feature = "nonblocking")]
+#[cfg(feature = "nonblocking-core")]
 use async_trait::async_trait;
+#[cfg(feature = "nonblocking-core")]
+use std::{future::Future, pin::Pin};
 
 /// Describes blocking Datadog network client
 pub trait DataDogClient {
     /// Sends collection of messages to DataDog
     fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError>;
+
+    /// Waits until logs passed to [`send`](Self::send) are delivered, for clients that deliver them in background.
+    /// Logger calls it when it is flushed and before it stops.
+    fn flush(&mut self) {}
+
+    /// Returns diagnostic messages collected since previous call, e.g. about switching endpoints.
+    /// Logger reports them to its self log after each attempt to send logs.
+    fn take_self_log(&mut self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// Describes asynchronous (non-blocking) DataDog client
-#[cfg(feature = "nonblocking")]
+#[cfg(feature = "nonblocking-core")]
 #[async_trait]
 pub trait AsyncDataDogClient {
     /// Sends logs to DataDog in a non-blocking fashion
     async fn send_async(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError>;
+
+    /// Waits until logs passed to [`send_async`](Self::send_async) are delivered, for clients that deliver them in background.
+    /// Logger calls it when it is flushed and before it stops.
+    ///
+    /// Implementations are written as `async fn flush_async(&mut self)` in `#[async_trait]` impl block.
+    // default spelled out, as `async_trait` would require `Self: Send` for it
+    fn flush_async<'life0, 'async_trait>(
+        &'life0 mut self,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async {})
+    }
+
+    /// Returns diagnostic messages collected since previous call, e.g. about switching endpoints.
+    /// Logger reports them to its self log after each attempt to send logs.
+    fn take_self_log(&mut self) -> Vec<String> {
+        Vec::new()
+    }
 }
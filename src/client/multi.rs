@@ -0,0 +1,333 @@
+#[cfg(feature = "nonblocking-core")]
+use super::AsyncDataDogClient;
+use super::DataDogClient;
+use crate::error::DataDogLoggerError;
+use crate::logger::{filter::LevelFilters, DataDogLog, DataDogLogLevel};
+#[cfg(feature = "nonblocking-core")]
+use async_trait::async_trait;
+use flume::{bounded, Receiver, Sender, TrySendError};
+use log::LevelFilter;
+use serde_json::Value;
+use std::{fmt, thread, time::Duration};
+
+/// Datadog client sending logs to several destinations, e.g. two DataDog organizations and a local file
+///
+/// Every destination runs its client on a thread of its own, with its own queue of batches and retries.
+/// Hence a slow or failing destination does not delay nor affect the others.
+/// When its queue is full, batches for that destination are dropped.
+///
+/// Since sending only hands batches over to destinations, it never blocks and fails only if no destination accepted a batch.
+/// Flushing the logger, e.g. with [`DataDogLoggerGuard`](crate::logger::DataDogLoggerGuard) or in panic hook,
+/// waits until destinations send queued batches, and so does dropping the client.
+/// Errors of destinations, prefixed with their names, and dropped batches are reported to self log of the logger.
+///
+/// # Examples
+///```rust
+///use datadog_logs::{
+///    client::{Destination, FileDataDogClient, HttpDataDogClient, MultiDataDogClient},
+///    config::DataDogConfig,
+///    logger::DataDogLogger,
+///};
+///use log::LevelFilter;
+///
+///let config = DataDogConfig::default();
+///let client = MultiDataDogClient::new()
+///    .destination(Destination::new("datadog", HttpDataDogClient::new(&config).unwrap()))
+///    .destination(
+///        Destination::new("compliance", FileDataDogClient::new(&config).unwrap())
+///            .level(LevelFilter::Warn),
+///    );
+///
+///let logger = DataDogLogger::blocking(client, config);
+///```
+pub struct MultiDataDogClient {
+    destinations: Vec<RunningDestination>,
+    errors: (Sender<String>, Receiver<String>),
+}
+
+/// Message passed to thread of a destination
+enum DestinationMessage {
+    Batch(Vec<DataDogLog>),
+    /// Request acknowledged once batches queued before it are sent
+    Flush(Sender<()>),
+}
+
+/// Destination of [`MultiDataDogClient`] with its client, filters and retry policy
+pub struct Destination {
+    name: String,
+    client: Box<dyn DataDogClient + Send>,
+    filters: LevelFilters,
+    max_retries: u32,
+    retry_backoff: Duration,
+    queue_capacity: usize,
+}
+
+struct RunningDestination {
+    name: String,
+    filters: LevelFilters,
+    batches: Option<Sender<DestinationMessage>>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl fmt::Debug for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Destination")
+            .field("name", &self.name)
+            .field("filters", &self.filters)
+            .field("max_retries", &self.max_retries)
+            .field("retry_backoff", &self.retry_backoff)
+            .field("queue_capacity", &self.queue_capacity)
+            .finish()
+    }
+}
+
+impl fmt::Debug for MultiDataDogClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self
+            .destinations
+            .iter()
+            .map(|destination| destination.name.as_str())
+            .collect();
+        f.debug_struct("MultiDataDogClient")
+            .field("destinations", &names)
+            .finish()
+    }
+}
+
+impl Destination {
+    /// Creates destination accepting all logs. Name identifies it in errors.
+    pub fn new<S: Into<String>, T: DataDogClient + Send + 'static>(name: S, client: T) -> Self {
+        Destination {
+            name: name.into(),
+            client: Box::new(client),
+            filters: LevelFilters::default(),
+            max_retries: 3,
+            retry_backoff: Duration::from_millis(100),
+            queue_capacity: 100,
+        }
+    }
+
+    /// Sets level of logs accepted by destination
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.filters.set_default(level);
+        self
+    }
+
+    /// Sets level of logs accepted by destination for given target and its submodules.
+    /// It takes precedence over [`level`](Self::level).
+    pub fn filter<S: Into<String>>(mut self, target: S, level: LevelFilter) -> Self {
+        self.filters.set_target(target.into(), level);
+        self
+    }
+
+    /// Sets number of retries of a failed batch and delay before first retry, doubled with every subsequent retry
+    pub fn retry(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Sets number of batches waiting to be sent, after which new batches are dropped
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity.max(1);
+        self
+    }
+
+    fn accepts(filters: &LevelFilters, log: &DataDogLog) -> bool {
+        let level = log
+            .level
+            .parse()
+            .unwrap_or(DataDogLogLevel::Informational)
+            .to_log_level();
+        let target = match log.attributes.get("logger") {
+            Some(Value::Object(logger)) => logger.get("name").and_then(Value::as_str),
+            _ => None,
+        };
+        filters.enabled(target.unwrap_or_default(), level)
+    }
+
+    fn run(mut self, messages: Receiver<DestinationMessage>, errors: Sender<String>) {
+        for message in messages {
+            let batch = match message {
+                DestinationMessage::Batch(batch) => batch,
+                DestinationMessage::Flush(ack) => {
+                    self.client.flush();
+                    self.report(&errors);
+                    ack.send(()).unwrap_or_default();
+                    continue;
+                }
+            };
+
+            let mut attempt = 0;
+            loop {
+                let result = self.client.send(&batch);
+                self.report(&errors);
+                let e = match result {
+                    Ok(()) => break,
+                    Err(e) => e,
+                };
+                errors
+                    .try_send(format!("{} : {}", self.name, e))
+                    .unwrap_or_default();
+                if attempt >= self.max_retries {
+                    errors
+                        .try_send(format!(
+                            "{} : Dropping {} logs after {} failed attempts",
+                            self.name,
+                            batch.len(),
+                            attempt + 1
+                        ))
+                        .unwrap_or_default();
+                    break;
+                }
+                thread::sleep(self.retry_backoff.saturating_mul(1 << attempt.min(16)));
+                attempt += 1;
+            }
+        }
+    }
+
+    /// Passes notices of the client on, prefixed with name of the destination
+    fn report(&mut self, errors: &Sender<String>) {
+        for notice in self.client.take_self_log() {
+            errors
+                .try_send(format!("{} : {}", self.name, notice))
+                .unwrap_or_default();
+        }
+    }
+}
+
+impl Default for MultiDataDogClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultiDataDogClient {
+    /// Creates client without destinations
+    pub fn new() -> Self {
+        MultiDataDogClient {
+            destinations: Vec::new(),
+            errors: bounded(100),
+        }
+    }
+
+    /// Adds destination and starts its thread
+    pub fn destination(mut self, destination: Destination) -> Self {
+        let (sender, receiver) = bounded(destination.queue_capacity);
+        let name = destination.name.clone();
+        let filters = destination.filters.clone();
+        let errors = self.errors.0.clone();
+        let handle = thread::spawn(move || destination.run(receiver, errors));
+
+        self.destinations.push(RunningDestination {
+            name,
+            filters,
+            batches: Some(sender),
+            handle: Some(handle),
+        });
+        self
+    }
+
+    /// Sends flush request to every destination and returns receivers of their acknowledgements
+    fn request_flush(&self) -> Vec<Receiver<()>> {
+        let mut acks = Vec::new();
+        for destination in &self.destinations {
+            if let Some(ref sender) = destination.batches {
+                let (ack_sender, ack_receiver) = bounded(1);
+                if sender.send(DestinationMessage::Flush(ack_sender)).is_ok() {
+                    acks.push(ack_receiver);
+                }
+            }
+        }
+        acks
+    }
+}
+
+impl DataDogClient for MultiDataDogClient {
+    fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        let mut accepted = false;
+        for destination in &self.destinations {
+            let batch: Vec<DataDogLog> = messages
+                .iter()
+                .filter(|log| Destination::accepts(&destination.filters, log))
+                .cloned()
+                .collect();
+            if batch.is_empty() {
+                accepted = true;
+                continue;
+            }
+
+            let sender = match destination.batches {
+                Some(ref sender) => sender,
+                None => continue,
+            };
+            let count = batch.len();
+            match sender.try_send(DestinationMessage::Batch(batch)) {
+                Ok(()) => accepted = true,
+                Err(TrySendError::Full(_)) => self
+                    .errors
+                    .0
+                    .try_send(format!(
+                        "{} : Dropping {} logs, queue is full",
+                        destination.name, count
+                    ))
+                    .unwrap_or_default(),
+                Err(TrySendError::Disconnected(_)) => self
+                    .errors
+                    .0
+                    .try_send(format!("{} : Destination thread stopped", destination.name))
+                    .unwrap_or_default(),
+            }
+        }
+
+        if accepted || self.destinations.is_empty() {
+            Ok(())
+        } else {
+            Err(DataDogLoggerError::OtherError(
+                "No destination accepted logs".into(),
+            ))
+        }
+    }
+
+    fn flush(&mut self) {
+        for ack in self.request_flush() {
+            ack.recv().unwrap_or_default();
+        }
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        self.errors.1.try_iter().collect()
+    }
+}
+
+#[cfg(feature = "nonblocking-core")]
+#[async_trait]
+impl AsyncDataDogClient for MultiDataDogClient {
+    async fn send_async(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        self.send(messages)
+    }
+
+    async fn flush_async(&mut self) {
+        for ack in self.request_flush() {
+            ack.recv_async().await.unwrap_or_default();
+        }
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        self.errors.1.try_iter().collect()
+    }
+}
+
+impl Drop for MultiDataDogClient {
+    fn drop(&mut self) {
+        // closing queues lets destinations finish sending queued batches and stop
+        for destination in self.destinations.iter_mut() {
+            destination.batches.take();
+        }
+        for destination in self.destinations.iter_mut() {
+            if let Some(handle) = destination.handle.take() {
+                handle.join().unwrap_or_default();
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+#[cfg(feature = "nonblocking-core")]
+use super::AsyncDataDogClient;
+use super::{tcp::TcpConnection, tls::TlsSettings, DataDogClient};
+use crate::config::DataDogConfig;
+use crate::error::DataDogLoggerError;
+use crate::logger::{timestamp::rfc3339, DataDogLog, DataDogLogLevel};
+#[cfg(feature = "nonblocking-core")]
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+use std::io::Write;
+use std::net::{TcpStream, UdpSocket};
+use std::time::SystemTime;
+
+/// Enterprise number reserved for documentation by RFC 5612, used in identifiers of structured data
+const ENTERPRISE_NUMBER: &str = "32473";
+/// Largest payload of UDP datagram over IPv4
+const MAX_UDP_PAYLOAD: usize = 65_507;
+const TRUNCATION_MARKER: &str = "...";
+
+/// Datadog client sending logs to a syslog relay as RFC 5424 messages over UDP, TCP or TLS
+///
+/// Levels are mapped to syslog severities. Tags and attributes of logs are sent as structured data elements
+/// `tags@32473` and `attributes@32473`, with nested attributes flattened to paths separated by dots.
+/// Messages sent over TCP and TLS are framed with octet counting. TLS requires `native-tls` feature.
+/// Relay and facility are configured with [`DataDogSyslogConfig`](crate::config::DataDogSyslogConfig).
+///
+/// Messages sent over UDP are truncated to fit a single datagram, or dropped if even an empty message does not fit.
+/// Connection is established lazily when first batch of logs is sent and reestablished after every failure.
+/// When sending datagrams fails partway, retry of the same batch sends only datagrams that were not sent yet.
+/// Nonblocking logger sends logs in its task as well.
+#[derive(Debug)]
+pub struct SyslogDataDogClient {
+    transport: SyslogTransport,
+    host: String,
+    port: u16,
+    facility: u8,
+    app_name: Option<String>,
+    process_id: String,
+    tls: Option<TlsSettings>,
+    udp: Option<UdpSocket>,
+    stream: Option<TcpConnection>,
+    // logs of the last batch that failed, its timestamp and number of datagrams that were sent
+    unsent: Option<(Vec<DataDogLog>, String, usize)>,
+    notices: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SyslogTransport {
+    Udp,
+    Tcp,
+    Tls,
+}
+
+impl SyslogDataDogClient {
+    /// Creates new DataDog syslog client
+    pub fn new(config: &DataDogConfig) -> Result<Self, DataDogLoggerError> {
+        let syslog_config = &config.syslog_config;
+        let invalid_url = || {
+            DataDogLoggerError::ConfigError(format!("Invalid syslog URL : {}", syslog_config.url))
+        };
+
+        let (scheme, address) = syslog_config
+            .url
+            .split_once("://")
+            .ok_or_else(invalid_url)?;
+        let transport = match scheme {
+            "udp" => SyslogTransport::Udp,
+            "tcp" => SyslogTransport::Tcp,
+            "tls" => SyslogTransport::Tls,
+            _ => {
+                return Err(DataDogLoggerError::ConfigError(format!(
+                    "Unsupported syslog protocol : {}",
+                    scheme
+                )))
+            }
+        };
+        let (host, port) = address.rsplit_once(':').ok_or_else(invalid_url)?;
+        let port = port.parse().map_err(|_| invalid_url())?;
+
+        Ok(SyslogDataDogClient {
+            transport,
+            host: host.trim_start_matches('[').trim_end_matches(']').into(),
+            port,
+            facility: syslog_config.facility.code(),
+            app_name: syslog_config.app_name.clone(),
+            process_id: std::process::id().to_string(),
+            tls: if transport == SyslogTransport::Tls {
+                Some(TlsSettings::new(&syslog_config.tls)?)
+            } else {
+                None
+            },
+            udp: None,
+            stream: None,
+            unsent: None,
+            notices: Vec::new(),
+        })
+    }
+
+    fn connect_udp(&self) -> Result<UdpSocket, DataDogLoggerError> {
+        let socket = UdpSocket::bind(if self.host.contains(':') {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        })?;
+        socket.connect((self.host.as_str(), self.port))?;
+        Ok(socket)
+    }
+
+    fn connect_stream(&self) -> Result<TcpConnection, DataDogLoggerError> {
+        let stream = TcpStream::connect((self.host.as_str(), self.port))?;
+        match self.tls {
+            Some(ref tls) => tls.connect(&self.host, stream),
+            None => Ok(TcpConnection::Plain(stream)),
+        }
+    }
+
+    /// Formats log as RFC 5424 message
+    fn format(&self, log: &DataDogLog, timestamp: &str) -> String {
+        let severity = log
+            .level
+            .parse()
+            .unwrap_or(DataDogLogLevel::Informational)
+            .severity();
+        let app_name = self.app_name.as_deref().unwrap_or(&log.service);
+
+        let mut message = format!(
+            "<{}>1 {} {} {} {} - ",
+            self.facility as u16 * 8 + severity as u16,
+            timestamp,
+            header_field(&log.host, 255),
+            header_field(app_name, 48),
+            self.process_id,
+        );
+
+        let mut structured_data = String::new();
+        if !log.ddtags.is_empty() {
+            structured_data.push_str("[tags@");
+            structured_data.push_str(ENTERPRISE_NUMBER);
+            for tag in log.ddtags.iter() {
+                let (name, value) = tag.split_once(':').unwrap_or((tag, ""));
+                push_parameter(&mut structured_data, name, value);
+            }
+            structured_data.push(']');
+        }
+        if !log.attributes.is_empty() {
+            structured_data.push_str("[attributes@");
+            structured_data.push_str(ENTERPRISE_NUMBER);
+            push_attributes(&mut structured_data, "", &log.attributes);
+            structured_data.push(']');
+        }
+        if structured_data.is_empty() {
+            structured_data.push('-');
+        }
+
+        message.push_str(&structured_data);
+        message.push(' ');
+        message.push_str(&log.message);
+        message
+    }
+
+    /// Formats log as RFC 5424 message not exceeding maximum size, truncating its message if needed
+    fn datagram(&mut self, log: &DataDogLog, timestamp: &str, max_size: usize) -> Option<String> {
+        let mut message = self.format(log, timestamp);
+        if message.len() <= max_size {
+            return Some(message);
+        }
+
+        // message of the log is the last part of the formatted one
+        let original = message.len();
+        let header = original - log.message.len();
+        if header + TRUNCATION_MARKER.len() <= max_size {
+            let mut length = max_size - header - TRUNCATION_MARKER.len();
+            while !log.message.is_char_boundary(length) {
+                length -= 1;
+            }
+            message.truncate(header + length);
+            message.push_str(TRUNCATION_MARKER);
+            self.notices.push(format!(
+                "Truncating message of log of {} bytes exceeding maximum datagram size of {} bytes",
+                original, max_size
+            ));
+            Some(message)
+        } else {
+            self.notices.push(format!(
+                "Dropping log of {} bytes exceeding maximum datagram size of {} bytes",
+                original, max_size
+            ));
+            None
+        }
+    }
+
+    fn send_udp(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        // retry of the failed batch keeps its timestamp and skips datagrams that were sent
+        let (timestamp, sent) = match self.unsent.take() {
+            Some((unsent, timestamp, sent)) if unsent == messages => (timestamp, sent),
+            _ => (rfc3339(SystemTime::now()), 0),
+        };
+        let datagrams: Vec<String> = messages
+            .iter()
+            .filter_map(|message| self.datagram(message, &timestamp, MAX_UDP_PAYLOAD))
+            .collect();
+
+        let socket = match self.udp {
+            Some(ref socket) => socket,
+            None => match self.connect_udp() {
+                Ok(socket) => self.udp.insert(socket),
+                Err(e) => {
+                    self.unsent = Some((messages.to_vec(), timestamp, sent));
+                    return Err(e);
+                }
+            },
+        };
+        for (index, datagram) in datagrams.iter().enumerate().skip(sent) {
+            if let Err(e) = socket.send(datagram.as_bytes()) {
+                self.udp = None;
+                self.unsent = Some((messages.to_vec(), timestamp, index));
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Header fields consist of printable ASCII characters only, `-` stands for an empty one
+fn header_field(value: &str, max_length: usize) -> String {
+    let value: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_length)
+        .collect();
+    if value.is_empty() {
+        "-".into()
+    } else {
+        value
+    }
+}
+
+fn push_attributes(structured_data: &mut String, prefix: &str, attributes: &Map<String, Value>) {
+    for (key, value) in attributes {
+        let name = format!("{}{}", prefix, key);
+        match value {
+            Value::Object(nested) => {
+                push_attributes(structured_data, &format!("{}.", name), nested)
+            }
+            Value::String(value) => push_parameter(structured_data, &name, value),
+            value => push_parameter(structured_data, &name, &value.to_string()),
+        }
+    }
+}
+
+/// Appends `name="value"` parameter, replacing characters not allowed in names and escaping value
+fn push_parameter(structured_data: &mut String, name: &str, value: &str) {
+    structured_data.push(' ');
+    structured_data.extend(
+        name.chars()
+            .map(|c| match c {
+                '=' | ']' | '"' => '_',
+                c if c.is_ascii_graphic() => c,
+                _ => '_',
+            })
+            .take(32),
+    );
+    structured_data.push_str("=\"");
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            structured_data.push('\\');
+        }
+        structured_data.push(c);
+    }
+    structured_data.push('"');
+}
+
+impl DataDogClient for SyslogDataDogClient {
+    fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        if self.transport == SyslogTransport::Udp {
+            return self.send_udp(messages);
+        }
+
+        // octet counting framing, see RFC 6587
+        let timestamp = rfc3339(SystemTime::now());
+        let mut payload = Vec::new();
+        for message in messages {
+            let message = self.format(message, &timestamp);
+            write!(payload, "{} {}", message.len(), message)?;
+        }
+
+        let stream = match self.stream {
+            Some(ref mut stream) => stream,
+            None => self.stream.insert(self.connect_stream()?),
+        };
+        let result = stream.write_all(&payload).and_then(|_| stream.flush());
+        if result.is_err() {
+            self.stream = None;
+        }
+
+        Ok(result?)
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.notices)
+    }
+}
+
+#[cfg(feature = "nonblocking-core")]
+#[async_trait]
+impl AsyncDataDogClient for SyslogDataDogClient {
+    async fn send_async(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        self.send(messages)
+    }
+
+    fn take_self_log(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.notices)
+    }
+}
@@ -0,0 +1,107 @@
+use super::{tls::TlsSettings, DataDogClient};
+use crate::config::{ApiKeySource, DataDogConfig};
+use crate::error::DataDogLoggerError;
+use crate::logger::DataDogLog;
+#[cfg(feature = "native-tls")]
+use native_tls::TlsStream;
+use std::convert::TryFrom;
+use std::io::Write;
+use std::net::TcpStream;
+
+/// Datadog network client using TCP protocol, optionally secured with TLS
+///
+/// Connection is established lazily when first batch of logs is sent and reestablished after every failure.
+/// TLS is configured with `tls` of [`DataDogTcpConfig`](crate::config::DataDogTcpConfig) and requires `native-tls` feature.
+#[derive(Debug)]
+pub struct TcpDataDogClient {
+    api_key: ApiKeySource,
+    domain: String,
+    port: u16,
+    tls: Option<TlsSettings>,
+    connection: Option<TcpConnection>,
+}
+
+#[derive(Debug)]
+pub(super) enum TcpConnection {
+    Plain(TcpStream),
+    #[cfg(feature = "native-tls")]
+    Tls(TlsStream<TcpStream>),
+}
+
+impl Write for TcpConnection {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            TcpConnection::Plain(stream) => stream.write(buf),
+            #[cfg(feature = "native-tls")]
+            TcpConnection::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            TcpConnection::Plain(stream) => stream.flush(),
+            #[cfg(feature = "native-tls")]
+            TcpConnection::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+impl TcpDataDogClient {
+    /// Creates new DataDog TCP logger
+    pub fn new(config: &DataDogConfig) -> Result<Self, DataDogLoggerError> {
+        let tcp_config = &config.tcp_config;
+        let port = if tcp_config.use_tls {
+            tcp_config.tls_port
+        } else {
+            tcp_config.non_tls_port
+        };
+
+        Ok(TcpDataDogClient {
+            api_key: config.apikey.clone(),
+            domain: tcp_config.domain.clone(),
+            port: u16::try_from(port)
+                .map_err(|_| DataDogLoggerError::ConfigError(format!("Invalid port : {}", port)))?,
+            tls: if tcp_config.use_tls {
+                Some(TlsSettings::new(&tcp_config.tls)?)
+            } else {
+                None
+            },
+            connection: None,
+        })
+    }
+
+    fn connect(&self) -> Result<TcpConnection, DataDogLoggerError> {
+        let stream = TcpStream::connect((self.domain.as_str(), self.port))?;
+        match self.tls {
+            Some(ref tls) => tls.connect(&self.domain, stream),
+            None => Ok(TcpConnection::Plain(stream)),
+        }
+    }
+}
+
+impl DataDogClient for TcpDataDogClient {
+    fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        let api_key = self.api_key.get()?;
+        let mut payload = Vec::new();
+        for message in messages {
+            payload.extend_from_slice(api_key.as_bytes());
+            payload.push(b' ');
+            serde_json::to_writer(&mut payload, message)?;
+            payload.push(b'\n');
+        }
+
+        let connection = match self.connection {
+            Some(ref mut connection) => connection,
+            None => self.connection.insert(self.connect()?),
+        };
+
+        let result = connection
+            .write_all(&payload)
+            .and_then(|_| connection.flush());
+        if result.is_err() {
+            self.connection = None;
+        }
+
+        Ok(result?)
+    }
+}
@@ -0,0 +1,109 @@
+use super::{check_pins, TlsMaterial};
+use crate::error::DataDogLoggerError;
+use ::legacy_rustls::{
+    client::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier, WebPkiVerifier},
+    Certificate, ClientConfig, DigitallySignedStruct, Error, OwnedTrustAnchor, PrivateKey,
+    RootCertStore, ServerName, SignatureScheme,
+};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+impl TlsMaterial {
+    /// Builds configuration of transports based on rustls 0.21, i.e. reqwest
+    pub(crate) fn legacy_rustls_config(&self) -> Result<ClientConfig, DataDogLoggerError> {
+        let mut roots = RootCertStore::empty();
+        if self.system_roots {
+            roots.add_trust_anchors(legacy_webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    anchor.subject,
+                    anchor.spki,
+                    anchor.name_constraints,
+                )
+            }));
+        }
+        for file in self.root_certificates.iter() {
+            for certificate in file.certificates()? {
+                roots
+                    .add(&Certificate(certificate))
+                    .map_err(|e| file.error(e))?;
+            }
+        }
+        let verifier = PinningVerifier {
+            verifier: WebPkiVerifier::new(roots, None),
+            pins: self.pins.clone(),
+        };
+
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(verifier));
+        match &self.identity {
+            Some((certificate, key)) => {
+                let chain = certificate
+                    .certificates()?
+                    .into_iter()
+                    .map(Certificate)
+                    .collect();
+                builder
+                    .with_client_auth_cert(chain, PrivateKey(key.pkcs8_key()?))
+                    .map_err(|e| certificate.error(e))
+            }
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+/// Verifies server certificate as usual and then checks its public key against pins
+struct PinningVerifier {
+    verifier: WebPkiVerifier,
+    pins: Vec<Vec<u8>>,
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, Error> {
+        let verified = self.verifier.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            scts,
+            ocsp_response,
+            now,
+        )?;
+        let domain = match server_name {
+            ServerName::DnsName(name) => name.as_ref().to_string(),
+            ServerName::IpAddress(address) => address.to_string(),
+            _ => "server".into(),
+        };
+        check_pins(&self.pins, &domain, &end_entity.0).map_err(Error::General)?;
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &Certificate,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.verifier.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &Certificate,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.verifier.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.verifier.supported_verify_schemes()
+    }
+}
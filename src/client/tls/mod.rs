@@ -0,0 +1,266 @@
+// without any TLS implementation settings are only read and validated
+#![cfg_attr(
+    not(any(
+        feature = "native-tls",
+        feature = "attohttpc",
+        feature = "ureq",
+        feature = "reqwest",
+        feature = "hyper"
+    )),
+    allow(dead_code)
+)]
+
+#[cfg(feature = "reqwest")]
+mod legacy_rustls;
+#[cfg(any(feature = "ureq", feature = "hyper"))]
+mod rustls;
+
+#[cfg(feature = "native-tls")]
+use super::tcp::TcpConnection;
+use crate::config::DataDogTlsConfig;
+use crate::error::DataDogLoggerError;
+#[cfg(feature = "pinning")]
+use base64::{engine::general_purpose::STANDARD, Engine};
+#[cfg(any(feature = "native-tls", feature = "attohttpc"))]
+use native_tls::Certificate;
+#[cfg(feature = "native-tls")]
+use native_tls::{Identity, TlsConnector};
+#[cfg(feature = "pinning")]
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::net::TcpStream;
+#[cfg(feature = "pinning")]
+use x509_cert::der::{Decode, Encode};
+
+/// Files and pins of [`DataDogTlsConfig`], read and validated when client is created
+#[derive(Debug, Clone)]
+pub(crate) struct TlsMaterial {
+    root_certificates: Vec<PemFile>,
+    system_roots: bool,
+    /// Client certificate chain and its PKCS #8 key
+    identity: Option<(PemFile, PemFile)>,
+    /// SHA-256 hashes of accepted public keys
+    pins: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Clone)]
+struct PemFile {
+    path: String,
+    contents: Vec<u8>,
+}
+
+impl TlsMaterial {
+    pub(crate) fn load(config: &DataDogTlsConfig) -> Result<Self, DataDogLoggerError> {
+        let root_certificates = config
+            .root_certificates
+            .iter()
+            .map(|path| PemFile::read(path))
+            .collect::<Result<_, _>>()?;
+
+        let identity = match (&config.client_certificate, &config.client_key) {
+            (Some(certificate), Some(key)) => {
+                Some((PemFile::read(certificate)?, PemFile::read(key)?))
+            }
+            (None, None) => None,
+            _ => {
+                return Err(DataDogLoggerError::ConfigError(
+                    "Client certificate and client key must be specified together".into(),
+                ))
+            }
+        };
+
+        #[cfg(feature = "pinning")]
+        let pins = config
+            .pinned_spki_sha256
+            .iter()
+            .map(|pin| {
+                STANDARD
+                    .decode(pin.trim())
+                    .ok()
+                    .filter(|hash| hash.len() == 32)
+                    .ok_or_else(|| {
+                        DataDogLoggerError::ConfigError(format!("Invalid SPKI pin : {}", pin))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        #[cfg(not(feature = "pinning"))]
+        let pins = match config.pinned_spki_sha256.is_empty() {
+            true => Vec::new(),
+            false => {
+                return Err(DataDogLoggerError::ConfigError(
+                    "SPKI pins require native-tls, ureq, reqwest or hyper feature".into(),
+                ))
+            }
+        };
+
+        let material = TlsMaterial {
+            root_certificates,
+            system_roots: !config.disable_system_roots,
+            identity,
+            pins,
+        };
+        // certificates are parsed up front, so that invalid ones are reported whichever client uses them
+        #[cfg(any(feature = "native-tls", feature = "attohttpc"))]
+        material.native_root_certificates()?;
+        #[cfg(not(any(feature = "native-tls", feature = "attohttpc")))]
+        #[cfg(any(feature = "ureq", feature = "reqwest", feature = "hyper"))]
+        for file in material.root_certificates.iter() {
+            file.certificates()?;
+        }
+        Ok(material)
+    }
+
+    /// Whether settings only add trusted root certificates, which every HTTP transport supports
+    #[cfg(feature = "attohttpc")]
+    pub(crate) fn only_adds_root_certificates(&self) -> bool {
+        self.system_roots && self.identity.is_none() && self.pins.is_empty()
+    }
+
+    #[cfg(any(feature = "native-tls", feature = "attohttpc"))]
+    pub(crate) fn native_root_certificates(&self) -> Result<Vec<Certificate>, DataDogLoggerError> {
+        let mut certificates = Vec::new();
+        for file in self.root_certificates.iter() {
+            let stack = Certificate::stack_from_pem(&file.contents).map_err(|e| file.error(e))?;
+            if stack.is_empty() {
+                return Err(file.error("no certificates found"));
+            }
+            certificates.extend(stack);
+        }
+        Ok(certificates)
+    }
+}
+
+impl PemFile {
+    fn read(path: &str) -> Result<Self, DataDogLoggerError> {
+        let contents = fs::read(path).map_err(|e| {
+            DataDogLoggerError::ConfigError(format!("Unable to read {} : {}", path, e))
+        })?;
+        Ok(PemFile {
+            path: path.into(),
+            contents,
+        })
+    }
+
+    fn error(&self, e: impl std::fmt::Display) -> DataDogLoggerError {
+        DataDogLoggerError::ConfigError(format!("Invalid {} : {}", self.path, e))
+    }
+
+    /// DER encoded certificates in the file
+    #[cfg(any(feature = "ureq", feature = "reqwest", feature = "hyper"))]
+    fn certificates(&self) -> Result<Vec<Vec<u8>>, DataDogLoggerError> {
+        let certificates =
+            rustls_pemfile::certs(&mut self.contents.as_slice()).map_err(|e| self.error(e))?;
+        if certificates.is_empty() {
+            return Err(self.error("no certificates found"));
+        }
+        Ok(certificates)
+    }
+
+    /// DER encoded PKCS #8 key in the file
+    #[cfg(any(feature = "ureq", feature = "reqwest", feature = "hyper"))]
+    fn pkcs8_key(&self) -> Result<Vec<u8>, DataDogLoggerError> {
+        rustls_pemfile::pkcs8_private_keys(&mut self.contents.as_slice())
+            .map_err(|e| self.error(e))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| self.error("no PKCS #8 key found"))
+    }
+}
+
+/// Checks that public key of DER encoded certificate matches one of pins, if there are any
+#[cfg(feature = "pinning")]
+fn check_pins(pins: &[Vec<u8>], domain: &str, certificate: &[u8]) -> Result<(), String> {
+    if pins.is_empty() {
+        return Ok(());
+    }
+    let public_key = x509_cert::Certificate::from_der(certificate)
+        .and_then(|certificate| certificate.tbs_certificate.subject_public_key_info.to_der())
+        .map_err(|e| {
+            format!(
+                "Unable to read public key of {} certificate : {}",
+                domain, e
+            )
+        })?;
+
+    let hash = Sha256::digest(public_key);
+    if pins.iter().any(|pin| pin[..] == hash[..]) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Public key of {} certificate does not match any pin : {}",
+            domain,
+            STANDARD.encode(hash)
+        ))
+    }
+}
+
+/// TLS connector of TCP and syslog clients along with pinned public keys
+#[cfg(feature = "native-tls")]
+#[derive(Debug, Clone)]
+pub(super) struct TlsSettings {
+    connector: TlsConnector,
+    pins: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "native-tls")]
+impl TlsSettings {
+    pub(super) fn new(config: &DataDogTlsConfig) -> Result<Self, DataDogLoggerError> {
+        let material = TlsMaterial::load(config)?;
+
+        let mut builder = TlsConnector::builder();
+        for certificate in material.native_root_certificates()? {
+            builder.add_root_certificate(certificate);
+        }
+        builder.disable_built_in_roots(!material.system_roots);
+        if let Some((certificate, key)) = &material.identity {
+            builder.identity(
+                Identity::from_pkcs8(&certificate.contents, &key.contents)
+                    .map_err(|e| certificate.error(e))?,
+            );
+        }
+
+        Ok(TlsSettings {
+            connector: builder.build()?,
+            pins: material.pins,
+        })
+    }
+
+    /// Performs TLS handshake and checks pinned public keys before anything is sent
+    pub(super) fn connect(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> Result<TcpConnection, DataDogLoggerError> {
+        let stream = self.connector.connect(domain, stream)?;
+        if !self.pins.is_empty() {
+            let certificate = stream.peer_certificate()?.ok_or_else(|| {
+                DataDogLoggerError::OtherError(format!("{} did not present a certificate", domain))
+            })?;
+            check_pins(&self.pins, domain, &certificate.to_der()?)
+                .map_err(DataDogLoggerError::OtherError)?;
+        }
+        Ok(TcpConnection::Tls(stream))
+    }
+}
+
+/// Stands in for TLS connector of TCP and syslog clients, which is not available without native-tls feature
+#[cfg(not(feature = "native-tls"))]
+#[derive(Debug, Clone)]
+pub(super) enum TlsSettings {}
+
+#[cfg(not(feature = "native-tls"))]
+impl TlsSettings {
+    pub(super) fn new(_config: &DataDogTlsConfig) -> Result<Self, DataDogLoggerError> {
+        Err(DataDogLoggerError::ConfigError(
+            "TLS of TCP and syslog clients requires native-tls feature".into(),
+        ))
+    }
+
+    pub(super) fn connect(
+        &self,
+        _domain: &str,
+        _stream: TcpStream,
+    ) -> Result<super::tcp::TcpConnection, DataDogLoggerError> {
+        match *self {}
+    }
+}
@@ -0,0 +1,112 @@
+use super::{check_pins, TlsMaterial};
+use crate::error::DataDogLoggerError;
+use ::rustls::{
+    client::{
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        WebPkiServerVerifier,
+    },
+    crypto::ring,
+    pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime},
+    ClientConfig, DigitallySignedStruct, Error, RootCertStore, SignatureScheme,
+};
+use std::sync::Arc;
+
+impl TlsMaterial {
+    /// Builds configuration of transports based on rustls 0.23, i.e. ureq and hyper
+    pub(crate) fn rustls_config(&self) -> Result<ClientConfig, DataDogLoggerError> {
+        let provider = Arc::new(ring::default_provider());
+
+        let mut roots = RootCertStore::empty();
+        if self.system_roots {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+        for file in self.root_certificates.iter() {
+            for certificate in file.certificates()? {
+                roots
+                    .add(CertificateDer::from(certificate))
+                    .map_err(|e| file.error(e))?;
+            }
+        }
+        let verifier = PinningVerifier {
+            verifier: WebPkiServerVerifier::builder_with_provider(
+                Arc::new(roots),
+                provider.clone(),
+            )
+            .build()
+            .map_err(|e| {
+                DataDogLoggerError::ConfigError(format!("Invalid root certificates : {}", e))
+            })?,
+            pins: self.pins.clone(),
+        };
+
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|e| DataDogLoggerError::ConfigError(e.to_string()))?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier));
+        match &self.identity {
+            Some((certificate, key)) => {
+                let chain = certificate
+                    .certificates()?
+                    .into_iter()
+                    .map(CertificateDer::from)
+                    .collect();
+                let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.pkcs8_key()?));
+                builder
+                    .with_client_auth_cert(chain, key)
+                    .map_err(|e| certificate.error(e))
+            }
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+/// Verifies server certificate as usual and then checks its public key against pins
+#[derive(Debug)]
+struct PinningVerifier {
+    verifier: Arc<WebPkiServerVerifier>,
+    pins: Vec<Vec<u8>>,
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        let verified = self.verifier.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        check_pins(&self.pins, &server_name.to_str(), end_entity).map_err(Error::General)?;
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.verifier.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.verifier.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.verifier.supported_verify_schemes()
+    }
+}
@@ -0,0 +1,66 @@
+use super::{HttpRequest, HttpResponse, HttpTransport};
+use crate::client::tls::TlsMaterial;
+use crate::config::DataDogTlsConfig;
+use crate::error::DataDogLoggerError;
+use ::attohttpc::{
+    header::{HeaderName, HeaderValue},
+    Session,
+};
+
+/// Blocking transport based on `attohttpc`
+#[derive(Debug, Default)]
+pub struct AttohttpcTransport {
+    session: Session,
+}
+
+impl AttohttpcTransport {
+    /// Creates new transport
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates transport sending requests with given session, e.g. with additional root certificates
+    pub fn with_session(session: Session) -> Self {
+        AttohttpcTransport { session }
+    }
+
+    /// Creates transport trusting additional root certificates, the only TLS settings attohttpc supports
+    pub fn with_tls(config: &DataDogTlsConfig) -> Result<Self, DataDogLoggerError> {
+        let tls = TlsMaterial::load(config)?;
+        if !tls.only_adds_root_certificates() {
+            return Err(DataDogLoggerError::ConfigError(
+                "attohttpc does not support client certificates, pins and disabling system roots"
+                    .into(),
+            ));
+        }
+        AttohttpcTransport::from_tls(&tls)
+    }
+
+    pub(super) fn from_tls(tls: &TlsMaterial) -> Result<Self, DataDogLoggerError> {
+        let mut session = Session::new();
+        for certificate in tls.native_root_certificates()? {
+            session.add_root_certificate(certificate);
+        }
+        Ok(AttohttpcTransport::with_session(session))
+    }
+}
+
+impl HttpTransport for AttohttpcTransport {
+    fn send(&mut self, request: &HttpRequest) -> Result<HttpResponse, DataDogLoggerError> {
+        let mut builder = self.session.post(&request.url);
+        for (name, value) in request.headers.iter() {
+            builder = builder.try_header_append(
+                HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                    DataDogLoggerError::ConfigError(format!("Invalid header name : {}", name))
+                })?,
+                HeaderValue::from_str(value).map_err(::attohttpc::Error::from)?,
+            )?;
+        }
+        let response = builder.bytes(&request.body).send()?;
+
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            body: response.text().unwrap_or_default(),
+        })
+    }
+}
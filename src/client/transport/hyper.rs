@@ -0,0 +1,75 @@
+use super::{AsyncHttpTransport, HttpRequest, HttpResponse};
+use crate::client::tls::TlsMaterial;
+use crate::config::DataDogTlsConfig;
+use crate::error::DataDogLoggerError;
+use ::hyper::{body::Bytes, Request};
+use async_trait::async_trait;
+use http_body_util::{BodyExt, Full};
+use hyper_rustls::{builderstates::WantsSchemes, HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+
+/// Nonblocking transport based on `hyper`, requires tokio runtime
+///
+/// Server certificates are verified against Mozilla root certificates, unless TLS settings say otherwise.
+#[derive(Debug)]
+pub struct HyperTransport {
+    client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
+}
+
+impl HyperTransport {
+    /// Creates new transport
+    pub fn new() -> Self {
+        HyperTransport::with_connector(HttpsConnectorBuilder::new().with_webpki_roots())
+    }
+
+    /// Creates transport applying TLS settings, the way [`DefaultHttpTransport`](crate::client::DefaultHttpTransport) does
+    pub fn with_tls(config: &DataDogTlsConfig) -> Result<Self, DataDogLoggerError> {
+        HyperTransport::from_tls(&TlsMaterial::load(config)?)
+    }
+
+    pub(super) fn from_tls(tls: &TlsMaterial) -> Result<Self, DataDogLoggerError> {
+        let builder = HttpsConnectorBuilder::new().with_tls_config(tls.rustls_config()?);
+        Ok(HyperTransport::with_connector(builder))
+    }
+
+    fn with_connector(builder: HttpsConnectorBuilder<WantsSchemes>) -> Self {
+        let connector = builder.https_or_http().enable_http1().build();
+        HyperTransport {
+            client: Client::builder(TokioExecutor::new()).build(connector),
+        }
+    }
+}
+
+impl Default for HyperTransport {
+    fn default() -> Self {
+        HyperTransport::new()
+    }
+}
+
+#[async_trait]
+impl AsyncHttpTransport for HyperTransport {
+    async fn send_async(
+        &mut self,
+        request: &HttpRequest,
+    ) -> Result<HttpResponse, DataDogLoggerError> {
+        let mut builder = Request::post(request.url.as_str());
+        for (name, value) in request.headers.iter() {
+            builder = builder.header(name, value);
+        }
+        let request = builder
+            .body(Full::new(Bytes::from(request.body.clone())))
+            .map_err(|e| DataDogLoggerError::HyperError(Box::new(e)))?;
+
+        let response = self.client.request(request).await?;
+        let status = response.status().as_u16();
+        let body = response.into_body().collect().await?.to_bytes();
+
+        Ok(HttpResponse {
+            status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
@@ -0,0 +1,258 @@
+#[cfg(feature = "attohttpc")]
+mod attohttpc;
+#[cfg(feature = "hyper")]
+mod hyper;
+#[cfg(feature = "reqwest")]
+mod reqwest;
+#[cfg(all(
+    feature = "nonblocking-core",
+    not(any(feature = "reqwest", feature = "hyper", feature = "blocking"))
+))]
+mod thread;
+#[cfg(feature = "blocking")]
+mod unblock;
+#[cfg(feature = "ureq")]
+mod ureq;
+
+#[cfg(feature = "attohttpc")]
+pub use self::attohttpc::AttohttpcTransport;
+#[cfg(feature = "hyper")]
+pub use self::hyper::HyperTransport;
+#[cfg(feature = "reqwest")]
+pub use self::reqwest::ReqwestTransport;
+#[cfg(feature = "blocking")]
+pub use self::unblock::UnblockHttpTransport;
+#[cfg(feature = "ureq")]
+pub use self::ureq::UreqTransport;
+
+use super::tls::TlsMaterial;
+use crate::config::DataDogHttpConfig;
+use crate::error::DataDogLoggerError;
+#[cfg(feature = "nonblocking-core")]
+use async_trait::async_trait;
+use std::fmt;
+use url::Url;
+
+/// HTTP request with logs, built by [`HttpDataDogClient`](crate::client::HttpDataDogClient)
+#[derive(Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// Url of DataDog intake
+    pub url: Url,
+    /// Headers of the request, including content type and API key
+    pub headers: Vec<(String, String)>,
+    /// Logs serialized as JSON array
+    pub body: Vec<u8>,
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                if name.eq_ignore_ascii_case("DD-API-KEY") {
+                    (name.as_str(), "<redacted>")
+                } else {
+                    (name.as_str(), value.as_str())
+                }
+            })
+            .collect();
+        f.debug_struct("HttpRequest")
+            .field("url", &self.url.as_str())
+            .field("headers", &headers)
+            .field("body", &String::from_utf8_lossy(&self.body))
+            .finish()
+    }
+}
+
+/// Response to [`HttpRequest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// Status code
+    pub status: u16,
+    /// Body, used in error messages
+    pub body: String,
+}
+
+/// Blocking HTTP transport of [`HttpDataDogClient`](crate::client::HttpDataDogClient)
+///
+/// Transport only delivers requests, so it returns responses with any status.
+/// Errors are reserved for failures to get a response.
+pub trait HttpTransport: Send {
+    /// Sends request and returns its response
+    fn send(&mut self, request: &HttpRequest) -> Result<HttpResponse, DataDogLoggerError>;
+}
+
+/// Nonblocking HTTP transport of [`HttpDataDogClient`](crate::client::HttpDataDogClient)
+///
+/// Transport only delivers requests, so it returns responses with any status.
+/// Errors are reserved for failures to get a response.
+#[cfg(feature = "nonblocking-core")]
+#[async_trait]
+pub trait AsyncHttpTransport: Send {
+    /// Sends request and returns its response
+    async fn send_async(
+        &mut self,
+        request: &HttpRequest,
+    ) -> Result<HttpResponse, DataDogLoggerError>;
+}
+
+/// Transport chosen by [`HttpDataDogClient::new`](crate::client::HttpDataDogClient::new) based on enabled features
+///
+/// Requests are sent with attohttpc or ureq, whichever is enabled first, and by nonblocking logger with reqwest or hyper.
+/// `tls` of [`DataDogHttpConfig`] is applied to all of them, except that attohttpc only supports additional
+/// root certificates, so ureq is used instead for other settings.
+///
+/// Without reqwest and hyper, nonblocking logger sends requests as blocking logger does. With `with-async-std`
+/// or `with-smol` features it does so on a thread pool, as [`UnblockHttpTransport`] does, otherwise on a new thread
+/// for each request, so that its task is never blocked.
+/// Since reqwest and hyper require tokio, loggers built for other runtimes by
+/// [`DataDogLoggerBuilder`](crate::logger::DataDogLoggerBuilder) use a thread pool even when they are enabled,
+/// while loggers created otherwise need [`UnblockHttpTransport`].
+pub struct DefaultHttpTransport {
+    // missing transport is reported when it is used, as long as there is the other one
+    blocking: Result<Box<dyn HttpTransport>, String>,
+    #[cfg(feature = "nonblocking-core")]
+    nonblocking: Result<Box<dyn AsyncHttpTransport>, String>,
+}
+
+impl fmt::Debug for DefaultHttpTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultHttpTransport")
+            .finish_non_exhaustive()
+    }
+}
+
+impl DefaultHttpTransport {
+    /// Creates transport for given configuration
+    pub fn new(config: &DataDogHttpConfig) -> Result<Self, DataDogLoggerError> {
+        Self::create(config, false)
+    }
+
+    /// Creates transport sending requests of nonblocking logger on a thread pool rather than with reqwest or hyper
+    #[cfg(any(feature = "with-async-std", feature = "with-smol"))]
+    pub(crate) fn unblocking(config: &DataDogHttpConfig) -> Result<Self, DataDogLoggerError> {
+        Self::create(config, true)
+    }
+
+    #[allow(unused_variables)]
+    fn create(config: &DataDogHttpConfig, unblock: bool) -> Result<Self, DataDogLoggerError> {
+        let tls = if config.tls.is_default() {
+            None
+        } else {
+            Some(TlsMaterial::load(&config.tls)?)
+        };
+        let blocking = blocking(tls.as_ref())?;
+
+        #[cfg(feature = "nonblocking-core")]
+        let nonblocking = nonblocking(tls.as_ref(), unblock)?;
+        #[cfg(not(feature = "nonblocking-core"))]
+        let nonblocking: Result<(), ()> = Err(());
+
+        if let (Err(e), Err(_)) = (&blocking, &nonblocking) {
+            return Err(DataDogLoggerError::ConfigError(e.clone()));
+        }
+        Ok(DefaultHttpTransport {
+            blocking,
+            #[cfg(feature = "nonblocking-core")]
+            nonblocking,
+        })
+    }
+}
+
+/// Returns message of missing transport as error, since it may not be needed
+#[allow(unused_variables)]
+fn blocking(
+    tls: Option<&TlsMaterial>,
+) -> Result<Result<Box<dyn HttpTransport>, String>, DataDogLoggerError> {
+    #[cfg(feature = "attohttpc")]
+    if tls.map_or(true, TlsMaterial::only_adds_root_certificates) {
+        let transport = match tls {
+            Some(tls) => AttohttpcTransport::from_tls(tls)?,
+            None => AttohttpcTransport::new(),
+        };
+        return Ok(Ok(Box::new(transport)));
+    }
+
+    #[cfg(feature = "ureq")]
+    let transport: Result<Box<dyn HttpTransport>, String> = Ok(Box::new(match tls {
+        Some(tls) => UreqTransport::from_tls(tls)?,
+        None => UreqTransport::new(),
+    }));
+    #[cfg(all(feature = "attohttpc", not(feature = "ureq")))]
+    let transport = Err(
+        "Client certificates, pins and disabling system roots require ureq feature for blocking requests"
+            .to_string(),
+    );
+    #[cfg(not(any(feature = "attohttpc", feature = "ureq")))]
+    let transport = Err("Blocking requests require attohttpc or ureq feature".to_string());
+
+    Ok(transport)
+}
+
+/// Returns message of missing transport as error, as long as blocking one is missing too
+#[cfg(feature = "nonblocking-core")]
+#[allow(unused_variables)]
+fn nonblocking(
+    tls: Option<&TlsMaterial>,
+    unblock: bool,
+) -> Result<Result<Box<dyn AsyncHttpTransport>, String>, DataDogLoggerError> {
+    // reqwest and hyper require tokio
+    #[cfg(feature = "blocking")]
+    if unblock {
+        return Ok(blocking(tls)?.map(unblocked));
+    }
+
+    #[cfg(feature = "reqwest")]
+    let transport: Result<Box<dyn AsyncHttpTransport>, String> = Ok(Box::new(match tls {
+        Some(tls) => ReqwestTransport::from_tls(tls)?,
+        None => ReqwestTransport::new(),
+    }));
+    #[cfg(all(feature = "hyper", not(feature = "reqwest")))]
+    let transport: Result<Box<dyn AsyncHttpTransport>, String> = Ok(Box::new(match tls {
+        Some(tls) => HyperTransport::from_tls(tls)?,
+        None => HyperTransport::new(),
+    }));
+    #[cfg(all(feature = "blocking", not(any(feature = "reqwest", feature = "hyper"))))]
+    let transport = blocking(tls)?.map(unblocked);
+    #[cfg(not(any(feature = "reqwest", feature = "hyper", feature = "blocking")))]
+    let transport: Result<Box<dyn AsyncHttpTransport>, String> = blocking(tls)?.map(|blocking| {
+        Box::new(thread::ThreadHttpTransport::new(blocking)) as Box<dyn AsyncHttpTransport>
+    });
+
+    Ok(transport)
+}
+
+#[cfg(feature = "blocking")]
+fn unblocked(transport: Box<dyn HttpTransport>) -> Box<dyn AsyncHttpTransport> {
+    Box::new(UnblockHttpTransport::new(transport))
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Box<T> {
+    fn send(&mut self, request: &HttpRequest) -> Result<HttpResponse, DataDogLoggerError> {
+        (**self).send(request)
+    }
+}
+
+impl HttpTransport for DefaultHttpTransport {
+    fn send(&mut self, request: &HttpRequest) -> Result<HttpResponse, DataDogLoggerError> {
+        match self.blocking {
+            Ok(ref mut transport) => transport.send(request),
+            Err(ref e) => Err(DataDogLoggerError::ConfigError(e.clone())),
+        }
+    }
+}
+
+#[cfg(feature = "nonblocking-core")]
+#[async_trait]
+impl AsyncHttpTransport for DefaultHttpTransport {
+    async fn send_async(
+        &mut self,
+        request: &HttpRequest,
+    ) -> Result<HttpResponse, DataDogLoggerError> {
+        match self.nonblocking {
+            Ok(ref mut transport) => transport.send_async(request).await,
+            Err(ref e) => Err(DataDogLoggerError::ConfigError(e.clone())),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use super::{AsyncHttpTransport, HttpRequest, HttpResponse};
+use crate::client::tls::TlsMaterial;
+use crate::config::DataDogTlsConfig;
+use crate::error::DataDogLoggerError;
+use async_trait::async_trait;
+
+/// Nonblocking transport based on `reqwest`
+#[derive(Debug, Default)]
+pub struct ReqwestTransport {
+    client: ::reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates new transport
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates transport sending requests with given client, e.g. configured with a proxy
+    pub fn with_client(client: ::reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+
+    /// Creates transport applying TLS settings, the way [`DefaultHttpTransport`](crate::client::DefaultHttpTransport) does
+    pub fn with_tls(config: &DataDogTlsConfig) -> Result<Self, DataDogLoggerError> {
+        ReqwestTransport::from_tls(&TlsMaterial::load(config)?)
+    }
+
+    pub(super) fn from_tls(tls: &TlsMaterial) -> Result<Self, DataDogLoggerError> {
+        let client = ::reqwest::Client::builder()
+            .use_preconfigured_tls(tls.legacy_rustls_config()?)
+            .build()?;
+        Ok(ReqwestTransport::with_client(client))
+    }
+}
+
+#[async_trait]
+impl AsyncHttpTransport for ReqwestTransport {
+    async fn send_async(
+        &mut self,
+        request: &HttpRequest,
+    ) -> Result<HttpResponse, DataDogLoggerError> {
+        let mut builder = self.client.post(request.url.clone());
+        for (name, value) in request.headers.iter() {
+            builder = builder.header(name, value);
+        }
+        let response = builder.body(request.body.clone()).send().await?;
+
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            body: response.text().await.unwrap_or_default(),
+        })
+    }
+}
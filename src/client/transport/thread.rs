@@ -0,0 +1,49 @@
+use super::{AsyncHttpTransport, HttpRequest, HttpResponse, HttpTransport};
+use crate::error::DataDogLoggerError;
+use async_trait::async_trait;
+use futures::channel::oneshot;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Nonblocking transport running a blocking one on a new thread for each request
+///
+/// It is used by [`DefaultHttpTransport`](super::DefaultHttpTransport) when there is no thread pool
+/// of [`UnblockHttpTransport`](super::UnblockHttpTransport) to run requests on.
+pub(super) struct ThreadHttpTransport<T> {
+    // shared with the thread sending request, which outlives the future if it is dropped
+    transport: Arc<Mutex<T>>,
+}
+
+impl<T> ThreadHttpTransport<T> {
+    pub(super) fn new(transport: T) -> Self {
+        ThreadHttpTransport {
+            transport: Arc::new(Mutex::new(transport)),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: HttpTransport + 'static> AsyncHttpTransport for ThreadHttpTransport<T> {
+    async fn send_async(
+        &mut self,
+        request: &HttpRequest,
+    ) -> Result<HttpResponse, DataDogLoggerError> {
+        let transport = self.transport.clone();
+        let request = request.clone();
+        let (sender, receiver) = oneshot::channel();
+        thread::Builder::new()
+            .name("datadog-logs-http".into())
+            .spawn(move || {
+                let response = transport
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .send(&request);
+                sender.send(response).unwrap_or_default();
+            })?;
+        receiver.await.unwrap_or_else(|_| {
+            Err(DataDogLoggerError::OtherError(
+                "Thread sending request panicked".into(),
+            ))
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use super::{AsyncHttpTransport, HttpRequest, HttpResponse, HttpTransport};
+use crate::error::DataDogLoggerError;
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Nonblocking transport running a blocking one on a thread pool, so it works with any runtime
+///
+/// # Examples
+///```rust
+///use datadog_logs::{
+///    client::{DefaultHttpTransport, HttpDataDogClient, UnblockHttpTransport},
+///    config::DataDogConfig,
+///};
+///
+///let config = DataDogConfig::default();
+///let transport = UnblockHttpTransport::new(DefaultHttpTransport::new(&config.http_config).unwrap());
+///let client = HttpDataDogClient::with_transport(&config, transport).unwrap();
+///```
+#[derive(Debug)]
+pub struct UnblockHttpTransport<T> {
+    // shared with the thread sending request, which outlives the future if it is dropped
+    transport: Arc<Mutex<T>>,
+}
+
+impl<T> UnblockHttpTransport<T> {
+    /// Wraps blocking transport
+    pub fn new(transport: T) -> Self {
+        UnblockHttpTransport {
+            transport: Arc::new(Mutex::new(transport)),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: HttpTransport + 'static> AsyncHttpTransport for UnblockHttpTransport<T> {
+    async fn send_async(
+        &mut self,
+        request: &HttpRequest,
+    ) -> Result<HttpResponse, DataDogLoggerError> {
+        let transport = self.transport.clone();
+        let request = request.clone();
+        blocking::unblock(move || {
+            transport
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .send(&request)
+        })
+        .await
+    }
+}
@@ -0,0 +1,62 @@
+use super::{HttpRequest, HttpResponse, HttpTransport};
+use crate::client::tls::TlsMaterial;
+use crate::config::DataDogTlsConfig;
+use crate::error::DataDogLoggerError;
+use std::sync::Arc;
+
+/// Blocking transport based on `ureq`
+#[derive(Debug)]
+pub struct UreqTransport {
+    agent: ::ureq::Agent,
+}
+
+impl UreqTransport {
+    /// Creates new transport
+    pub fn new() -> Self {
+        UreqTransport::with_agent(::ureq::Agent::new())
+    }
+
+    /// Creates transport sending requests with given agent, e.g. configured with a proxy
+    pub fn with_agent(agent: ::ureq::Agent) -> Self {
+        UreqTransport { agent }
+    }
+
+    /// Creates transport applying TLS settings, the way [`DefaultHttpTransport`](crate::client::DefaultHttpTransport) does
+    pub fn with_tls(config: &DataDogTlsConfig) -> Result<Self, DataDogLoggerError> {
+        UreqTransport::from_tls(&TlsMaterial::load(config)?)
+    }
+
+    pub(super) fn from_tls(tls: &TlsMaterial) -> Result<Self, DataDogLoggerError> {
+        let agent = ::ureq::AgentBuilder::new()
+            .tls_config(Arc::new(tls.rustls_config()?))
+            .build();
+        Ok(UreqTransport::with_agent(agent))
+    }
+}
+
+impl Default for UreqTransport {
+    fn default() -> Self {
+        UreqTransport::new()
+    }
+}
+
+impl HttpTransport for UreqTransport {
+    fn send(&mut self, request: &HttpRequest) -> Result<HttpResponse, DataDogLoggerError> {
+        let mut builder = self.agent.post(request.url.as_str());
+        for (name, value) in request.headers.iter() {
+            builder = builder.set(name, value);
+        }
+
+        // statuses other than 2xx are returned as errors by ureq, while they are responses for the client
+        let response = match builder.send_bytes(&request.body) {
+            Ok(response) => response,
+            Err(::ureq::Error::Status(_, response)) => response,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(HttpResponse {
+            status: response.status(),
+            body: response.into_string().unwrap_or_default(),
+        })
+    }
+}
@@ -1,18 +1,53 @@
-use serde::{Deserialize, Serialize};
+use crate::{
+    client::HttpRequest,
+    error::DataDogLoggerError,
+    hostname,
+    logger::{DataDogLogLevel, Tags},
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::default::Default;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
 /// Configuration for DataDogLogger
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataDogConfig {
     /// Tags to add to each log.
-    pub tags: Option<String>,
-    /// DataDog API key.
+    /// They can be specified as a comma separated string, a list of tags or a map of keys and values.
+    #[serde(default)]
+    pub tags: Tags,
+    /// DataDog API key, given directly or read from environment variable, file or custom provider.
     /// It is required to specify API key. Not doing it is considered an error.
-    pub apikey: String,
+    pub apikey: ApiKeySource,
     /// Service name to add to each log.
     pub service: Option<String>,
     /// Hostname to add to each log.
+    /// If not set, it is detected when logger is created, unless `detect_hostname` is disabled.
     pub hostname: Option<String>,
+    /// Enables detection of hostname when it is not set explicitly. Enabled by default.
+    ///
+    /// Hostname is taken from the first available of: `DD_HOSTNAME` environment variable,
+    /// `POD_NAME`, `KUBERNETES_POD_NAME` or `HOSTNAME` environment variables set in containers and pods,
+    /// `/etc/hostname` file and hostname reported by operating system.
+    /// Detected hostname is stored in `hostname` of logger configuration.
+    #[serde(default = "default_detect_hostname")]
+    pub detect_hostname: bool,
+    /// Environment the service runs in, e.g. `prod`.
+    /// If not set, it is read from `DD_ENV` environment variable when logger is created.
+    /// It is sent as `env` attribute and tag of each log, replacing `env` tag of `tags`.
+    #[serde(default)]
+    pub env: Option<String>,
+    /// Version of the service.
+    /// If not set, it is read from `DD_VERSION` environment variable when logger is created.
+    /// It is sent as `version` attribute and tag of each log, replacing `version` tag of `tags`.
+    /// [`builder!`](crate::builder!) defaults it to version of the calling crate.
+    #[serde(default)]
+    pub version: Option<String>,
     /// Source to add to each log.
     /// Default value is `rust`.
     #[serde(default)]
@@ -25,10 +60,23 @@ pub struct DataDogConfig {
     /// TCP client specific configuration
     /// It only needs to be specified for TCP logging in case of non-default settings.
     /// Otherwise default is assumed.
-    ///
-    /// Even though the crate does not support TCP client now, config is here to be an extensibility point.
     #[serde(default)]
     pub tcp_config: DataDogTcpConfig,
+    /// File client specific configuration
+    /// It only needs to be specified for logging to a file in case of non-default settings.
+    /// Otherwise default is assumed.
+    #[serde(default)]
+    pub file_config: DataDogFileConfig,
+    /// Local DataDog agent client specific configuration
+    /// It only needs to be specified for logging to the agent in case of non-default settings.
+    /// Otherwise default is assumed.
+    #[serde(default)]
+    pub agent_config: DataDogAgentConfig,
+    /// Syslog client specific configuration
+    /// It only needs to be specified for logging to syslog in case of non-default settings.
+    /// Otherwise default is assumed.
+    #[serde(default)]
+    pub syslog_config: DataDogSyslogConfig,
     /// Capacity of channel connecting logger thread with other threads.
     /// If not set explicitly, it defaults to 10 000 messages.
     /// If explicitly set to `None`, channel will be unbounded.
@@ -37,24 +85,242 @@ pub struct DataDogConfig {
     /// Enables or disables self logging. Disabled by default.
     #[serde(default)]
     pub enable_self_log: bool,
+    /// Maximum number of logs sent to DataDog in a single request.
+    /// Defaults to 50.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Number of times sending of a batch is retried before the batch is dropped.
+    /// Defaults to 3.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry in milliseconds. It is doubled with every subsequent retry.
+    /// Defaults to 100 milliseconds.
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Maximum time in milliseconds that flushing the logger waits for queued logs to be sent.
+    /// Defaults to 5 seconds.
+    #[serde(default = "default_flush_timeout_ms")]
+    pub flush_timeout_ms: u64,
+    /// Redaction of sensitive data performed by logger thread or task before logs are sent.
+    /// Disabled by default.
+    #[serde(default)]
+    pub redaction: DataDogRedactionConfig,
+    /// Sampling rules applied before logs are queued. The first rule matching a log decides whether it is kept.
+    /// Logs that do not match any rule are always kept.
+    #[serde(default)]
+    pub sampling: Vec<DataDogSamplingRule>,
+    /// Collapsing of repeated logs performed by logger thread or task.
+    /// Disabled by default.
+    #[serde(default)]
+    pub deduplication: Option<DataDogDeduplicationConfig>,
+}
+
+impl DataDogConfig {
+    pub(crate) fn retry_backoff(&self, attempt: u32) -> Duration {
+        Duration::from_millis(self.retry_backoff_ms.saturating_mul(1 << attempt.min(16)))
+    }
+
+    pub(crate) fn flush_timeout(&self) -> Duration {
+        Duration::from_millis(self.flush_timeout_ms)
+    }
+
+    /// Fills settings taken from environment and sets unified service tags, replacing configured ones.
+    /// It is done once, when logger is created.
+    pub(crate) fn resolve(mut self) -> Self {
+        self.env = self.env.or_else(|| env::var("DD_ENV").ok());
+        self.version = self.version.or_else(|| env::var("DD_VERSION").ok());
+        if self.hostname.is_none() && self.detect_hostname {
+            self.hostname = hostname::detect();
+        }
+        if let Some(ref env) = self.env {
+            self.tags
+                .replace("env", env)
+                .expect("env tag starts with a letter");
+        }
+        if let Some(ref version) = self.version {
+            self.tags
+                .replace("version", version)
+                .expect("version tag starts with a letter");
+        }
+        self
+    }
+}
+
+fn default_detect_hostname() -> bool {
+    true
+}
+
+fn default_batch_size() -> usize {
+    50
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    100
+}
+
+fn default_flush_timeout_ms() -> u64 {
+    5_000
 }
 
 impl Default for DataDogConfig {
     fn default() -> Self {
         DataDogConfig {
-            tags: None,
-            apikey: "".into(),
+            tags: Tags::new(),
+            apikey: Default::default(),
             service: None,
             hostname: None,
+            detect_hostname: default_detect_hostname(),
+            env: None,
+            version: None,
             http_config: Default::default(),
             tcp_config: Default::default(),
+            file_config: Default::default(),
+            agent_config: Default::default(),
+            syslog_config: Default::default(),
             source: "rust".into(),
             messages_channel_capacity: Some(10_000),
             enable_self_log: false,
+            batch_size: default_batch_size(),
+            max_retries: default_max_retries(),
+            retry_backoff_ms: default_retry_backoff_ms(),
+            flush_timeout_ms: default_flush_timeout_ms(),
+            redaction: Default::default(),
+            sampling: Vec::new(),
+            deduplication: None,
+        }
+    }
+}
+
+/// Configuration of sensitive data redaction
+///
+/// Rules are applied to log message and to all string values of its attributes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataDogRedactionConfig {
+    /// Enables built-in rules redacting e-mail addresses, payment card numbers, bearer tokens and DataDog API key of the logger.
+    /// API key is read from its source for every log, so keys are redacted after rotation as well, along with keys used before.
+    #[serde(default)]
+    pub builtin_rules: bool,
+    /// Custom rules applied after built-in ones.
+    #[serde(default)]
+    pub rules: Vec<DataDogRedactionRule>,
+}
+
+/// Custom redaction rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDogRedactionRule {
+    /// Regular expression matching sensitive data, in syntax of `regex` crate.
+    pub pattern: String,
+    /// Text that replaces every match. It may refer to capture groups e.g. `$1`.
+    /// Default value is `[REDACTED]`.
+    #[serde(default = "default_redaction_replacement")]
+    pub replacement: String,
+}
+
+impl DataDogRedactionRule {
+    /// Creates rule replacing matches of pattern with `[REDACTED]`
+    pub fn new<S: Into<String>>(pattern: S) -> Self {
+        DataDogRedactionRule {
+            pattern: pattern.into(),
+            replacement: default_redaction_replacement(),
+        }
+    }
+}
+
+fn default_redaction_replacement() -> String {
+    "[REDACTED]".into()
+}
+
+/// Sampling rule keeping only a fraction of matching logs
+///
+/// Kept logs are stamped with `sample_rate` attribute.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDogSamplingRule {
+    /// Target the rule applies to, along with its submodules. If not set, the rule applies to all targets.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// The most severe level the rule applies to. Less severe levels are sampled as well.
+    /// If not set, the rule applies to all levels.
+    #[serde(default)]
+    pub level: Option<DataDogLogLevel>,
+    /// Fraction of logs kept, between 0 and 1.
+    pub sample_rate: f64,
+    /// Name of attribute used for deterministic sampling.
+    /// Logs with the same value of the attribute, e.g. request id, are either all kept or all dropped.
+    /// If not set or attribute is missing, logs are sampled randomly.
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+impl DataDogSamplingRule {
+    /// Creates rule randomly keeping given fraction of all logs
+    pub fn new(sample_rate: f64) -> Self {
+        DataDogSamplingRule {
+            target: None,
+            level: None,
+            sample_rate,
+            key: None,
+        }
+    }
+
+    /// Restricts rule to target and its submodules
+    pub fn target<S: Into<String>>(mut self, target: S) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Restricts rule to given level and less severe ones
+    pub fn level(mut self, level: DataDogLogLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Makes sampling deterministic, based on value of given attribute
+    pub fn key<S: Into<String>>(mut self, key: S) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+}
+
+/// Configuration of repeated logs collapsing
+///
+/// Logs with the same key fields received within a window are sent as a single log.
+/// It has `repeat_count`, `first_timestamp` and `last_timestamp` attributes if it was repeated.
+/// Every log is delayed by the window, so it should be kept short.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDogDeduplicationConfig {
+    /// Window in milliseconds, counted from the first occurrence of a log.
+    /// Defaults to 1 second.
+    #[serde(default = "default_deduplication_window_ms")]
+    pub window_ms: u64,
+    /// Fields identifying repeated logs.
+    /// It can be `message`, `level`, `service`, `host`, `source`, `tags`, `target` or a path of an attribute with segments separated by dots.
+    ///
+    /// Default value is `["message", "level", "target"]`.
+    #[serde(default = "default_deduplication_key_fields")]
+    pub key_fields: Vec<String>,
+}
+
+impl Default for DataDogDeduplicationConfig {
+    fn default() -> Self {
+        DataDogDeduplicationConfig {
+            window_ms: default_deduplication_window_ms(),
+            key_fields: default_deduplication_key_fields(),
         }
     }
 }
 
+fn default_deduplication_window_ms() -> u64 {
+    1_000
+}
+
+fn default_deduplication_key_fields() -> Vec<String> {
+    vec!["message".into(), "level".into(), "target".into()]
+}
+
 /// HTTP specific Datadog connectivity configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataDogHttpConfig {
@@ -67,12 +333,91 @@ pub struct DataDogHttpConfig {
     /// Default value is `https://http-intake.logs.datadoghq.com/v1/input`.
     #[serde(default)]
     pub url: String,
+    /// Urls used in order when the previous one fails, e.g. intake of another region.
+    /// Empty by default.
+    #[serde(default)]
+    pub fallback_urls: Vec<String>,
+    /// Number of consecutive failures of an url after which the next one is used.
+    /// Only connection errors, server errors and throttling count as failures.
+    /// Defaults to 3.
+    #[serde(default = "default_failover_threshold")]
+    pub failover_threshold: u32,
+    /// Interval in milliseconds in which `url` is probed while a fallback url is used.
+    /// Logs are sent to `url` again once it succeeds. Defaults to 30 seconds.
+    #[serde(default = "default_failback_probe_interval_ms")]
+    pub failback_probe_interval_ms: u64,
+    /// TLS settings of HTTPS urls, applied by transport as described in [`DefaultHttpTransport`](crate::client::DefaultHttpTransport).
+    /// System defaults are used if not specified.
+    #[serde(default)]
+    pub tls: DataDogTlsConfig,
+    /// Headers added to every request, e.g. required by a gateway.
+    /// They replace default headers of the same name, e.g. `User-Agent` which defaults to `datadog-logs/<version>`.
+    ///
+    /// Values are visible in `Debug` output and serialized configuration,
+    /// so secrets are better added with `header_hook`.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Hook adding headers to each request. It is not serialized.
+    #[serde(skip)]
+    pub header_hook: Option<DataDogHeaderHook>,
+}
+
+/// Hook returning headers of a request, e.g. a short-lived token or a signature of its body
+///
+/// Returned headers replace headers of the same name already present in the request.
+///
+/// # Examples
+///```rust
+///use datadog_logs::config::{DataDogConfig, DataDogHeaderHook};
+///
+///let mut config = DataDogConfig::default();
+///config.http_config.header_hook = Some(DataDogHeaderHook::new(|request| {
+///    vec![("X-Body-Length".into(), request.body.len().to_string())]
+///}));
+///```
+#[derive(Clone)]
+pub struct DataDogHeaderHook(Arc<HeaderFn>);
+
+type HeaderFn = dyn Fn(&HttpRequest) -> Vec<(String, String)> + Send + Sync;
+
+impl DataDogHeaderHook {
+    /// Creates hook from a function
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&HttpRequest) -> Vec<(String, String)> + Send + Sync + 'static,
+    {
+        DataDogHeaderHook(Arc::new(hook))
+    }
+
+    pub(crate) fn headers(&self, request: &HttpRequest) -> Vec<(String, String)> {
+        (self.0)(request)
+    }
+}
+
+impl fmt::Debug for DataDogHeaderHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DataDogHeaderHook")
+    }
+}
+
+fn default_failover_threshold() -> u32 {
+    3
+}
+
+fn default_failback_probe_interval_ms() -> u64 {
+    30_000
 }
 
 impl Default for DataDogHttpConfig {
     fn default() -> Self {
         DataDogHttpConfig {
             url: "https://http-intake.logs.datadoghq.com/v1/input".into(),
+            fallback_urls: Vec::new(),
+            failover_threshold: default_failover_threshold(),
+            failback_probe_interval_ms: default_failback_probe_interval_ms(),
+            tls: Default::default(),
+            headers: BTreeMap::new(),
+            header_hook: None,
         }
     }
 }
@@ -95,6 +440,9 @@ pub struct DataDogTcpConfig {
     /// Port for encrypted connections. It defaults to 443.
     #[serde(default)]
     pub tls_port: usize,
+    /// TLS settings used when `use_tls` is set. System defaults are used if not specified.
+    #[serde(default)]
+    pub tls: DataDogTlsConfig,
 }
 
 impl Default for DataDogTcpConfig {
@@ -106,6 +454,439 @@ impl Default for DataDogTcpConfig {
             domain: "intake.logs.datadoghq.com".into(),
             non_tls_port: 10514,
             tls_port: 443,
+            tls: Default::default(),
         }
     }
 }
+
+/// TLS settings shared by network clients, e.g. for a gateway with a private CA or mutual TLS
+///
+/// Certificates and keys are read when client is created, so invalid ones are reported early.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataDogTlsConfig {
+    /// Paths of PEM files with additional trusted root certificates.
+    /// A file may contain several certificates.
+    #[serde(default)]
+    pub root_certificates: Vec<String>,
+    /// If set, only `root_certificates` are trusted, not the system ones. Disabled by default.
+    /// HTTP transports based on rustls use Mozilla root certificates as the system ones.
+    #[serde(default)]
+    pub disable_system_roots: bool,
+    /// Path of PEM file with client certificate for mutual TLS, optionally followed by its chain.
+    /// Requires `client_key`.
+    #[serde(default)]
+    pub client_certificate: Option<String>,
+    /// Path of PEM file with PKCS #8 private key of `client_certificate`.
+    #[serde(default)]
+    pub client_key: Option<String>,
+    /// Base64 encoded SHA-256 hashes of subject public key info of accepted server certificates,
+    /// the same as `pin-sha256` of HPKP. If not empty, connection is closed before sending anything
+    /// unless public key of the server certificate matches one of them.
+    #[serde(default)]
+    pub pinned_spki_sha256: Vec<String>,
+}
+
+impl DataDogTlsConfig {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == DataDogTlsConfig::default()
+    }
+}
+
+/// Configuration of file written by [`FileDataDogClient`](crate::client::FileDataDogClient), e.g. to be tailed by DataDog agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDogFileConfig {
+    /// Path of the file logs are written to. It is created if it does not exist.
+    /// Default value is `datadog.log`.
+    #[serde(default = "default_file_path")]
+    pub path: String,
+    /// Size in bytes after which the file is rotated. Not rotated by size by default.
+    #[serde(default)]
+    pub max_size_bytes: Option<u64>,
+    /// Time in milliseconds after which the file is rotated. Not rotated by time by default.
+    #[serde(default)]
+    pub rotation_interval_ms: Option<u64>,
+    /// Number of rotated files that are kept, named `<path>.1` (the newest) to `<path>.<max_files>`.
+    /// Defaults to 5.
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+    /// When written logs are synchronized to disk. By default it is left to operating system.
+    #[serde(default)]
+    pub fsync: DataDogFsyncPolicy,
+}
+
+fn default_file_path() -> String {
+    "datadog.log".into()
+}
+
+fn default_max_files() -> usize {
+    5
+}
+
+impl Default for DataDogFileConfig {
+    fn default() -> Self {
+        DataDogFileConfig {
+            path: default_file_path(),
+            max_size_bytes: None,
+            rotation_interval_ms: None,
+            max_files: default_max_files(),
+            fsync: Default::default(),
+        }
+    }
+}
+
+/// Policy of synchronizing logs written to file with disk
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataDogFsyncPolicy {
+    /// Never synchronize explicitly
+    #[default]
+    Never,
+    /// Synchronize after every batch of logs
+    EveryBatch,
+    /// Synchronize after a batch when at least given number of milliseconds passed since previous synchronization
+    Interval(u64),
+}
+
+/// Configuration of connection to DataDog agent listening for logs, used by [`AgentDataDogClient`](crate::client::AgentDataDogClient)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDogAgentConfig {
+    /// Address of the agent listener with scheme selecting protocol, one of:
+    /// * `udp://<host>:<port>`,
+    /// * `tcp://<host>:<port>`,
+    /// * `unix://<path>` for Unix stream socket,
+    /// * `unixgram://<path>` for Unix datagram socket.
+    ///
+    /// Default value is `udp://127.0.0.1:10518`.
+    #[serde(default = "default_agent_url")]
+    pub url: String,
+    /// Maximum size in bytes of a single datagram sent over UDP or Unix datagram socket.
+    /// Logs are split into as many datagrams as needed. Message of a log larger than that is truncated to fit,
+    /// or the log is dropped if it does not fit even without message. Both are reported to self log.
+    /// UDP datagrams are further limited to 65507 bytes.
+    /// Defaults to 9000, which is default frame size of the agent.
+    #[serde(default = "default_max_datagram_size")]
+    pub max_datagram_size: usize,
+}
+
+fn default_agent_url() -> String {
+    "udp://127.0.0.1:10518".into()
+}
+
+fn default_max_datagram_size() -> usize {
+    9000
+}
+
+impl Default for DataDogAgentConfig {
+    fn default() -> Self {
+        DataDogAgentConfig {
+            url: default_agent_url(),
+            max_datagram_size: default_max_datagram_size(),
+        }
+    }
+}
+
+/// Configuration of syslog relay receiving logs from [`SyslogDataDogClient`](crate::client::SyslogDataDogClient)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDogSyslogConfig {
+    /// Address of the relay with scheme selecting protocol, one of:
+    /// * `udp://<host>:<port>`,
+    /// * `tcp://<host>:<port>`,
+    /// * `tls://<host>:<port>`.
+    ///
+    /// Default value is `udp://127.0.0.1:514`.
+    #[serde(default = "default_syslog_url")]
+    pub url: String,
+    /// Facility of sent messages. Defaults to `user`.
+    #[serde(default)]
+    pub facility: DataDogSyslogFacility,
+    /// Application name of sent messages. Defaults to service of the log.
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// TLS settings of `tls://` url. System defaults are used if not specified.
+    #[serde(default)]
+    pub tls: DataDogTlsConfig,
+}
+
+fn default_syslog_url() -> String {
+    "udp://127.0.0.1:514".into()
+}
+
+impl Default for DataDogSyslogConfig {
+    fn default() -> Self {
+        DataDogSyslogConfig {
+            url: default_syslog_url(),
+            facility: Default::default(),
+            app_name: None,
+            tls: Default::default(),
+        }
+    }
+}
+
+/// Syslog facility, see [RFC 5424](https://datatracker.ietf.org/doc/html/rfc5424#section-6.2.1)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataDogSyslogFacility {
+    /// Kernel messages
+    Kern,
+    /// User-level messages
+    #[default]
+    User,
+    /// Mail system
+    Mail,
+    /// System daemons
+    Daemon,
+    /// Security and authorization messages
+    Auth,
+    /// Messages generated internally by syslogd
+    Syslog,
+    /// Line printer subsystem
+    Lpr,
+    /// Network news subsystem
+    News,
+    /// UUCP subsystem
+    Uucp,
+    /// Clock daemon
+    Cron,
+    /// Private security and authorization messages
+    Authpriv,
+    /// FTP daemon
+    Ftp,
+    /// NTP subsystem
+    Ntp,
+    /// Log audit
+    Audit,
+    /// Log alert
+    Alert,
+    /// Clock daemon
+    Clock,
+    /// Local use 0
+    Local0,
+    /// Local use 1
+    Local1,
+    /// Local use 2
+    Local2,
+    /// Local use 3
+    Local3,
+    /// Local use 4
+    Local4,
+    /// Local use 5
+    Local5,
+    /// Local use 6
+    Local6,
+    /// Local use 7
+    Local7,
+}
+
+impl DataDogSyslogFacility {
+    /// Returns numerical code of the facility
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Provides DataDog API key, e.g. fetched from a secret store
+///
+/// Key is requested before every batch of logs is sent and, if built-in redaction rules are enabled,
+/// for every log to redact it, so implementations should cache it.
+pub trait ApiKeyProvider: Send + Sync {
+    /// Returns current API key
+    fn api_key(&self) -> Result<String, DataDogLoggerError>;
+}
+
+impl<F> ApiKeyProvider for F
+where
+    F: Fn() -> Result<String, DataDogLoggerError> + Send + Sync,
+{
+    fn api_key(&self) -> Result<String, DataDogLoggerError> {
+        self()
+    }
+}
+
+/// Source of DataDog API key
+///
+/// Clients read the key from its source before sending every batch of logs,
+/// so a rotated key is used without restarting the logger.
+///
+/// Key is never revealed by `Debug` or `Serialize`. Key given directly or by a provider is serialized as `<redacted>`,
+/// other sources as their description. It is deserialized from a string with key itself,
+/// `{ env: <variable> }` or `{ file: <path> }`. Deserializing `<redacted>` fails, so that configuration
+/// serialized earlier is not loaded with a wrong key.
+///
+/// # Examples
+///```rust
+///use datadog_logs::config::ApiKeySource;
+///
+///let literal: ApiKeySource = "<api key>".into();
+///let from_env = ApiKeySource::env("DD_API_KEY");
+///let from_file = ApiKeySource::file("/run/secrets/datadog-api-key");
+///
+///assert_eq!("ApiKeySource::Literal(<redacted>)", format!("{:?}", literal));
+///```
+#[derive(Clone)]
+pub struct ApiKeySource {
+    kind: ApiKeyKind,
+}
+
+#[derive(Clone)]
+enum ApiKeyKind {
+    Literal(String),
+    Env(String),
+    File(PathBuf, Arc<Mutex<Option<CachedFile>>>),
+    Provider(Arc<dyn ApiKeyProvider>),
+}
+
+/// Key read from file along with metadata used to tell whether file changed
+struct CachedFile {
+    modified: SystemTime,
+    len: u64,
+    key: String,
+}
+
+impl ApiKeySource {
+    /// Uses given key
+    pub fn literal<S: Into<String>>(key: S) -> Self {
+        ApiKeySource {
+            kind: ApiKeyKind::Literal(key.into()),
+        }
+    }
+
+    /// Reads key from environment variable every time it is needed
+    pub fn env<S: Into<String>>(variable: S) -> Self {
+        ApiKeySource {
+            kind: ApiKeyKind::Env(variable.into()),
+        }
+    }
+
+    /// Reads key from file, e.g. a mounted secret. File is read again once it is modified.
+    /// Surrounding whitespace is trimmed.
+    pub fn file<P: Into<PathBuf>>(path: P) -> Self {
+        ApiKeySource {
+            kind: ApiKeyKind::File(path.into(), Default::default()),
+        }
+    }
+
+    /// Requests key from custom provider
+    pub fn provider<P: ApiKeyProvider + 'static>(provider: P) -> Self {
+        ApiKeySource {
+            kind: ApiKeyKind::Provider(Arc::new(provider)),
+        }
+    }
+
+    /// Returns current key
+    pub fn get(&self) -> Result<String, DataDogLoggerError> {
+        match self.kind {
+            ApiKeyKind::Literal(ref key) => Ok(key.clone()),
+            ApiKeyKind::Env(ref variable) => env::var(variable).map_err(|_| {
+                DataDogLoggerError::ConfigError(format!(
+                    "API key environment variable {} is not set",
+                    variable
+                ))
+            }),
+            ApiKeyKind::File(ref path, ref cache) => {
+                let read_error = |e: std::io::Error| {
+                    DataDogLoggerError::ConfigError(format!(
+                        "Unable to read API key from {} : {}",
+                        path.display(),
+                        e
+                    ))
+                };
+                let metadata = fs::metadata(path).map_err(read_error)?;
+                let modified = metadata.modified().map_err(read_error)?;
+
+                let mut cache = cache
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                match *cache {
+                    Some(ref cached)
+                        if cached.modified == modified && cached.len == metadata.len() =>
+                    {
+                        Ok(cached.key.clone())
+                    }
+                    _ => {
+                        let key = fs::read_to_string(path)
+                            .map_err(read_error)?
+                            .trim()
+                            .to_string();
+                        *cache = Some(CachedFile {
+                            modified,
+                            len: metadata.len(),
+                            key: key.clone(),
+                        });
+                        Ok(key)
+                    }
+                }
+            }
+            ApiKeyKind::Provider(ref provider) => provider.api_key(),
+        }
+    }
+}
+
+impl Default for ApiKeySource {
+    fn default() -> Self {
+        ApiKeySource::literal("")
+    }
+}
+
+impl From<String> for ApiKeySource {
+    fn from(key: String) -> Self {
+        ApiKeySource::literal(key)
+    }
+}
+
+impl From<&str> for ApiKeySource {
+    fn from(key: &str) -> Self {
+        ApiKeySource::literal(key)
+    }
+}
+
+impl fmt::Debug for ApiKeySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ApiKeyKind::Literal(_) => f.write_str("ApiKeySource::Literal(<redacted>)"),
+            ApiKeyKind::Env(ref variable) => write!(f, "ApiKeySource::Env({:?})", variable),
+            ApiKeyKind::File(ref path, _) => write!(f, "ApiKeySource::File({:?})", path),
+            ApiKeyKind::Provider(_) => f.write_str("ApiKeySource::Provider"),
+        }
+    }
+}
+
+/// Placeholder serialized instead of literal keys and keys of providers
+const REDACTED_API_KEY: &str = "<redacted>";
+
+/// Serialized form of [`ApiKeySource`]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ApiKeyRepr {
+    Literal(String),
+    Env { env: String },
+    File { file: PathBuf },
+}
+
+impl Serialize for ApiKeySource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self.kind {
+            ApiKeyKind::Literal(_) | ApiKeyKind::Provider(_) => {
+                ApiKeyRepr::Literal(REDACTED_API_KEY.into())
+            }
+            ApiKeyKind::Env(ref variable) => ApiKeyRepr::Env {
+                env: variable.clone(),
+            },
+            ApiKeyKind::File(ref path, _) => ApiKeyRepr::File { file: path.clone() },
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiKeySource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match ApiKeyRepr::deserialize(deserializer)? {
+            // configuration serialized earlier has the key redacted, it must not be taken for a key
+            ApiKeyRepr::Literal(key) if key == REDACTED_API_KEY => return Err(de::Error::custom(
+                "API key was redacted when configuration was serialized, it has to be set again",
+            )),
+            ApiKeyRepr::Literal(key) => ApiKeySource::literal(key),
+            ApiKeyRepr::Env { env } => ApiKeySource::env(env),
+            ApiKeyRepr::File { file } => ApiKeySource::file(file),
+        })
+    }
+}
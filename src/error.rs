@@ -14,13 +14,23 @@ pub enum DataDogLoggerError {
     ConfigError(String),
     /// Generic error container
     OtherError(String),
-    /// Http logger error
+    /// Http error of attohttpc transport
+    #[cfg(feature = "attohttpc")]
     HttpError(attohttpc::Error),
+    /// TLS error of TCP and syslog clients
+    #[cfg(feature = "native-tls")]
+    TlsError(native_tls::Error),
     /// Error that can happen during DataDogLogger initialization with log
     LogIntegrationError(log::SetLoggerError),
-    /// Http error in non blocking client
-    #[cfg(feature = "nonblocking")]
+    /// Http error of reqwest transport
+    #[cfg(feature = "reqwest")]
     AsyncHttpError(reqwest::Error),
+    /// Http error of ureq transport
+    #[cfg(feature = "ureq")]
+    UreqError(Box<ureq::Error>),
+    /// Http error of hyper transport
+    #[cfg(feature = "hyper")]
+    HyperError(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl Display for DataDogLoggerError {
@@ -31,10 +41,17 @@ impl Display for DataDogLoggerError {
             DataDogLoggerError::IoError(e) => write!(f, "{}", e),
             DataDogLoggerError::ConfigError(e) => write!(f, "{}", e),
             DataDogLoggerError::OtherError(e) => write!(f, "{}", e),
+            #[cfg(feature = "attohttpc")]
             DataDogLoggerError::HttpError(e) => write!(f, "{}", e),
+            #[cfg(feature = "native-tls")]
+            DataDogLoggerError::TlsError(e) => write!(f, "{}", e),
             DataDogLoggerError::LogIntegrationError(e) => write!(f, "{}", e),
-            #[cfg(feature = "nonblocking")]
+            #[cfg(feature = "reqwest")]
             DataDogLoggerError::AsyncHttpError(e) => write!(f, "{}", e),
+            #[cfg(feature = "ureq")]
+            DataDogLoggerError::UreqError(e) => write!(f, "{}", e),
+            #[cfg(feature = "hyper")]
+            DataDogLoggerError::HyperError(e) => write!(f, "{}", e),
         }
     }
 }
@@ -57,21 +74,62 @@ impl From<std::io::Error> for DataDogLoggerError {
     }
 }
 
+#[cfg(feature = "attohttpc")]
 impl From<attohttpc::Error> for DataDogLoggerError {
     fn from(e: attohttpc::Error) -> Self {
         DataDogLoggerError::HttpError(e)
     }
 }
 
+#[cfg(feature = "native-tls")]
+impl From<native_tls::Error> for DataDogLoggerError {
+    fn from(e: native_tls::Error) -> Self {
+        DataDogLoggerError::TlsError(e)
+    }
+}
+
+#[cfg(feature = "native-tls")]
+impl<S> From<native_tls::HandshakeError<S>> for DataDogLoggerError {
+    fn from(e: native_tls::HandshakeError<S>) -> Self {
+        match e {
+            native_tls::HandshakeError::Failure(e) => DataDogLoggerError::TlsError(e),
+            native_tls::HandshakeError::WouldBlock(_) => {
+                DataDogLoggerError::IoError(std::io::ErrorKind::WouldBlock.into())
+            }
+        }
+    }
+}
+
 impl From<log::SetLoggerError> for DataDogLoggerError {
     fn from(e: log::SetLoggerError) -> Self {
         DataDogLoggerError::LogIntegrationError(e)
     }
 }
 
-#[cfg(feature = "nonblocking")]
+#[cfg(feature = "reqwest")]
 impl From<reqwest::Error> for DataDogLoggerError {
     fn from(e: reqwest::Error) -> Self {
         DataDogLoggerError::AsyncHttpError(e)
     }
 }
+
+#[cfg(feature = "ureq")]
+impl From<ureq::Error> for DataDogLoggerError {
+    fn from(e: ureq::Error) -> Self {
+        DataDogLoggerError::UreqError(Box::new(e))
+    }
+}
+
+#[cfg(feature = "hyper")]
+impl From<hyper_util::client::legacy::Error> for DataDogLoggerError {
+    fn from(e: hyper_util::client::legacy::Error) -> Self {
+        DataDogLoggerError::HyperError(Box::new(e))
+    }
+}
+
+#[cfg(feature = "hyper")]
+impl From<hyper::Error> for DataDogLoggerError {
+    fn from(e: hyper::Error) -> Self {
+        DataDogLoggerError::HyperError(Box::new(e))
+    }
+}
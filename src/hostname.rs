@@ -0,0 +1,22 @@
+use std::{env, fs};
+
+/// Environment variables holding name of the container or pod, in order of precedence
+const CONTAINER_VARIABLES: [&str; 3] = ["POD_NAME", "KUBERNETES_POD_NAME", "HOSTNAME"];
+
+/// Detects hostname of the machine.
+///
+/// It is taken from the first available of: `DD_HOSTNAME` environment variable, container or pod name
+/// environment variables, `/etc/hostname` file and hostname reported by operating system.
+pub(crate) fn detect() -> Option<String> {
+    std::iter::once("DD_HOSTNAME")
+        .chain(CONTAINER_VARIABLES)
+        .find_map(|variable| non_empty(env::var(variable).ok()))
+        .or_else(|| non_empty(fs::read_to_string("/etc/hostname").ok()))
+        .or_else(|| non_empty(gethostname::gethostname().into_string().ok()))
+}
+
+fn non_empty(hostname: Option<String>) -> Option<String> {
+    hostname
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+}
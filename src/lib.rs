@@ -15,6 +15,7 @@
 //!use datadog_logs::{config::DataDogConfig, logger::DataDogLogger, client::HttpDataDogClient};
 //!use log::*;
 //!
+//!# #[cfg(feature = "nonblocking-core")]
 //!# async fn func() {
 //!let config = DataDogConfig::default();
 //!let client = HttpDataDogClient::new(&config).unwrap();
@@ -30,6 +31,24 @@
 //!warn!("A warning");
 //!# }
 //!```
+//!
+//! # Using builder
+//!
+//! [`DataDogLogger::builder`](logger::DataDogLogger::builder) configures transport, mode, filters, batching and retries in one place.
+//!
+//!```rust
+//!use datadog_logs::logger::DataDogLogger;
+//!use log::*;
+//!
+//!let guard = DataDogLogger::builder()
+//!    .apikey("<api key>")
+//!    .level(LevelFilter::Info)
+//!    .batch_size(100)
+//!    .install_blocking()
+//!    .unwrap();
+//!
+//!info!("An info");
+//!```
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 #![warn(missing_debug_implementations)]
@@ -39,5 +58,8 @@ pub mod client;
 pub mod config;
 /// Errors
 pub mod error;
+mod hostname;
 /// DataDog logger implementations
 pub mod logger;
+/// Processors of logs run before they are sent
+pub mod processor;
@@ -0,0 +1,92 @@
+use super::logger::DataDogLogger;
+use crate::config::DataDogConfig;
+use log::{Log, Record};
+use log4rs::{
+    append::Append,
+    config::{Deserialize, Deserializers},
+};
+
+/// `log4rs` appender sending records to DataDog
+///
+/// It wraps [`DataDogLogger`](crate::logger::DataDogLogger), so records are batched and sent by its thread.
+///
+/// # Examples
+///```rust
+///use datadog_logs::logger::{DataDogAppender, DataDogLogger};
+///use log4rs::config::{Appender, Config, Root};
+///use log::LevelFilter;
+///
+///let logger = DataDogLogger::builder().build_blocking().unwrap();
+///let config = Config::builder()
+///    .appender(Appender::builder().build("datadog", Box::new(DataDogAppender::new(logger))))
+///    .build(Root::builder().appender("datadog").build(LevelFilter::Info))
+///    .unwrap();
+///```
+#[derive(Debug)]
+pub struct DataDogAppender {
+    logger: DataDogLogger,
+}
+
+impl DataDogAppender {
+    /// Creates new appender sending records with given logger
+    pub fn new(logger: DataDogLogger) -> Self {
+        DataDogAppender { logger }
+    }
+}
+
+impl Append for DataDogAppender {
+    fn append(&self, record: &Record) -> anyhow::Result<()> {
+        Log::log(&self.logger, record);
+        Ok(())
+    }
+
+    fn flush(&self) {
+        Log::flush(&self.logger);
+    }
+}
+
+/// Deserializer of [`DataDogAppender`](crate::logger::DataDogAppender) for `log4rs` configuration files
+///
+/// Appender accepts the same fields as [`DataDogConfig`](crate::config::DataDogConfig) and uses blocking HTTP logger.
+///
+/// # Examples
+///
+/// Deserializer has to be registered before configuration is loaded:
+///```rust
+///use datadog_logs::logger::DataDogAppenderDeserializer;
+///use log4rs::config::Deserializers;
+///
+///let mut deserializers = Deserializers::default();
+///deserializers.insert("datadog", DataDogAppenderDeserializer);
+///```
+///
+/// Then appender can be declared in YAML file:
+///```yaml
+///appenders:
+///  datadog:
+///    kind: datadog
+///    apikey: <api key>
+///    service: my-service
+///    http_config:
+///      url: https://http-intake.logs.datadoghq.eu/v1/input
+///```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DataDogAppenderDeserializer;
+
+impl Deserialize for DataDogAppenderDeserializer {
+    type Trait = dyn Append;
+
+    type Config = DataDogConfig;
+
+    fn deserialize(
+        &self,
+        config: DataDogConfig,
+        _deserializers: &Deserializers,
+    ) -> anyhow::Result<Box<dyn Append>> {
+        let logger = DataDogLogger::builder()
+            .config(config)
+            .build_blocking()
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(Box::new(DataDogAppender::new(logger)))
+    }
+}
@@ -1,36 +1,74 @@
-use super::log::DataDogLog;
+use super::{log::DataDogLog, message::LoggerMessage, pipeline::Pipeline, redaction::Redactor};
 use crate::client::DataDogClient;
-use flume::{Receiver, Sender, TryRecvError};
+use crate::config::DataDogConfig;
+use crate::processor::LogProcessor;
+use flume::{Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::{sync::Arc, thread};
 
 pub(crate) fn logger_thread<T: DataDogClient>(
     mut client: T,
-    logs: Receiver<DataDogLog>,
+    logs: Receiver<LoggerMessage>,
     mut selflog: Option<Sender<String>>,
+    config: DataDogConfig,
+    processors: Vec<Box<dyn LogProcessor>>,
+    redactor: Option<Arc<Redactor>>,
 ) {
     let mut store: Vec<DataDogLog> = Vec::new();
+    let mut pipeline = Pipeline::new(&config, processors, redactor);
 
     loop {
-        match logs.try_recv() {
-            Ok(msg) => {
-                if store.len() < 50 {
-                    store.push(msg);
-                } else {
-                    store.push(msg);
-                    send(&mut client, &mut store, &mut selflog);
+        let message = match logs.try_recv() {
+            Ok(message) => Some(message),
+            Err(TryRecvError::Empty) => {
+                pipeline.expire(&mut store);
+                send(&mut client, &mut store, &mut selflog, &config);
+                // blocking explicitly not to spin CPU, but only until pipeline has logs to release
+                match pipeline.next_deadline() {
+                    Some(deadline) => match logs.recv_deadline(deadline) {
+                        Ok(message) => Some(message),
+                        Err(RecvTimeoutError::Timeout) => None,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    },
+                    None => match logs.recv() {
+                        Ok(message) => Some(message),
+                        Err(_) => break,
+                    },
                 }
             }
-            Err(TryRecvError::Empty) => {
-                send(&mut client, &mut store, &mut selflog);
-                // blocking explicitly not to spin CPU
-                if let Ok(msg) = logs.recv() {
-                    store.push(msg);
+            Err(TryRecvError::Disconnected) => break,
+        };
+
+        match message {
+            Some(LoggerMessage::Log(msg)) => {
+                pipeline.process(msg, &mut store);
+                if store.len() >= config.batch_size {
+                    send(&mut client, &mut store, &mut selflog, &config);
                 }
             }
-            Err(TryRecvError::Disconnected) => {
-                send(&mut client, &mut store, &mut selflog);
-                break;
+            Some(LoggerMessage::Flush(ack)) => {
+                pipeline.drain(&mut store);
+                send(&mut client, &mut store, &mut selflog, &config);
+                flush(&mut client, &mut selflog);
+                ack.send(()).unwrap_or_default();
             }
-        };
+            None => {
+                // pipeline deadline passed, logs are released in the next iteration
+            }
+        }
+    }
+
+    pipeline.drain(&mut store);
+    send(&mut client, &mut store, &mut selflog, &config);
+    flush(&mut client, &mut selflog);
+}
+
+/// Waits until client delivers logs it was given and reports its notices
+fn flush<T: DataDogClient>(client: &mut T, selflog: &mut Option<Sender<String>>) {
+    client.flush();
+    for message in client.take_self_log() {
+        if let Some(selflog) = selflog {
+            selflog.try_send(message).unwrap_or_default();
+        }
     }
 }
 
@@ -38,15 +76,41 @@ fn send<T: DataDogClient>(
     client: &mut T,
     messages: &mut Vec<DataDogLog>,
     selflog: &mut Option<Sender<String>>,
+    config: &DataDogConfig,
 ) {
-    match client.send(&messages) {
-        Ok(_) => {
-            messages.clear();
-        }
-        Err(e) => {
+    for batch in messages.chunks(config.batch_size.max(1)) {
+        let mut attempt = 0;
+        loop {
+            let result = client.send(batch);
+            // notices are taken even without self log, so that client does not accumulate them
+            for message in client.take_self_log() {
+                if let Some(selflog) = selflog {
+                    selflog.try_send(message).unwrap_or_default();
+                }
+            }
+            let e = match result {
+                Ok(()) => break,
+                Err(e) => e,
+            };
             if let Some(selflog) = selflog {
                 selflog.try_send(e.to_string()).unwrap_or_default();
             }
+            if attempt >= config.max_retries {
+                if let Some(selflog) = selflog {
+                    selflog
+                        .try_send(format!(
+                            "Dropping {} logs after {} failed attempts",
+                            batch.len(),
+                            attempt + 1
+                        ))
+                        .unwrap_or_default();
+                }
+                break;
+            }
+            thread::sleep(config.retry_backoff(attempt));
+            attempt += 1;
         }
     }
+
+    messages.clear();
 }
@@ -0,0 +1,485 @@
+use super::{
+    filter::LevelFilters, logger::DataDogLogger, redaction::Redactor, sampling::Sampler,
+    tags::Tags, tee::LocalSink,
+};
+#[cfg(feature = "nonblocking-core")]
+use crate::client::AsyncDataDogClient;
+use crate::{
+    client::{
+        AgentDataDogClient, DataDogClient, FileDataDogClient, HttpDataDogClient, HttpRequest,
+        SyslogDataDogClient, TcpDataDogClient,
+    },
+    config::{
+        ApiKeySource, DataDogConfig, DataDogDeduplicationConfig, DataDogHeaderHook,
+        DataDogRedactionRule, DataDogSamplingRule,
+    },
+    error::DataDogLoggerError,
+    processor::LogProcessor,
+};
+#[cfg(feature = "nonblocking-core")]
+use futures::Future;
+use log::LevelFilter;
+use std::{fmt, time::Duration};
+
+type ClientFactory<C> = Box<dyn FnOnce(&DataDogConfig) -> Result<C, DataDogLoggerError>>;
+
+/// Builder of [`DataDogLogger`](crate::logger::DataDogLogger)
+///
+/// Collects configuration, transport and filters first and creates network client only when logger is built.
+/// Thanks to that client always uses the same configuration as the logger.
+///
+/// # Examples
+///```rust
+///use datadog_logs::logger::DataDogLogger;
+///use log::*;
+///
+///let guard = DataDogLogger::builder()
+///    .apikey("<api key>")
+///    .service("my-service")
+///    .level(LevelFilter::Info)
+///    .filter("hyper", LevelFilter::Warn)
+///    .install_blocking()
+///    .unwrap();
+///
+///info!("Application started");
+///
+/// // logs are flushed when guard is dropped
+///std::mem::drop(guard);
+///```
+pub struct DataDogLoggerBuilder<C = HttpDataDogClient> {
+    config: DataDogConfig,
+    client: ClientFactory<C>,
+    // client of `http` for runtimes other than tokio, which reqwest and hyper require
+    #[cfg(any(feature = "with-async-std", feature = "with-smol"))]
+    unblocking: Option<ClientFactory<C>>,
+    filters: LevelFilters,
+    local_sink: Option<LocalSink>,
+    processors: Vec<Box<dyn LogProcessor>>,
+}
+
+impl<C> fmt::Debug for DataDogLoggerBuilder<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataDogLoggerBuilder")
+            .field("config", &self.config)
+            .field("filters", &self.filters)
+            .field("local_sink", &self.local_sink)
+            .field("processors", &self.processors.len())
+            .finish()
+    }
+}
+
+impl Default for DataDogLoggerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataDogLoggerBuilder {
+    /// Creates builder with default configuration and HTTP transport
+    pub fn new() -> Self {
+        DataDogLoggerBuilder {
+            config: DataDogConfig::default(),
+            client: Box::new(HttpDataDogClient::new),
+            #[cfg(any(feature = "with-async-std", feature = "with-smol"))]
+            unblocking: Some(Box::new(HttpDataDogClient::unblocking)),
+            filters: LevelFilters::default(),
+            local_sink: None,
+            processors: Vec::new(),
+        }
+    }
+}
+
+impl<C: 'static> DataDogLoggerBuilder<C> {
+    /// Replaces whole configuration of the logger
+    pub fn config(mut self, config: DataDogConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets DataDog API key or its source, see [`ApiKeySource`](crate::config::ApiKeySource)
+    pub fn apikey<S: Into<ApiKeySource>>(mut self, apikey: S) -> Self {
+        self.config.apikey = apikey.into();
+        self
+    }
+
+    /// Sets service name added to each log
+    pub fn service<S: Into<String>>(mut self, service: S) -> Self {
+        self.config.service = Some(service.into());
+        self
+    }
+
+    /// Sets hostname added to each log
+    pub fn hostname<S: Into<String>>(mut self, hostname: S) -> Self {
+        self.config.hostname = Some(hostname.into());
+        self
+    }
+
+    /// Enables or disables detection of hostname when it is not set explicitly.
+    /// See [`DataDogConfig::detect_hostname`](crate::config::DataDogConfig::detect_hostname) for details.
+    pub fn detect_hostname(mut self, enable: bool) -> Self {
+        self.config.detect_hostname = enable;
+        self
+    }
+
+    /// Sets environment of the service, e.g. `prod`. It takes precedence over `DD_ENV` environment variable.
+    pub fn env<S: Into<String>>(mut self, env: S) -> Self {
+        self.config.env = Some(env.into());
+        self
+    }
+
+    /// Sets version of the service. It takes precedence over `DD_VERSION` environment variable.
+    pub fn version<S: Into<String>>(mut self, version: S) -> Self {
+        self.config.version = Some(version.into());
+        self
+    }
+
+    /// Sets tags added to each log
+    pub fn tags(mut self, tags: Tags) -> Self {
+        self.config.tags = tags;
+        self
+    }
+
+    /// Sets source added to each log
+    pub fn source<S: Into<String>>(mut self, source: S) -> Self {
+        self.config.source = source.into();
+        self
+    }
+
+    /// Sets capacity of channel connecting logger with its thread or task. `None` makes channel unbounded.
+    pub fn channel_capacity(mut self, capacity: Option<usize>) -> Self {
+        self.config.messages_channel_capacity = capacity;
+        self
+    }
+
+    /// Enables or disables self logging
+    pub fn self_log(mut self, enable: bool) -> Self {
+        self.config.enable_self_log = enable;
+        self
+    }
+
+    /// Sets maximum number of logs sent in a single request
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.config.batch_size = batch_size;
+        self
+    }
+
+    /// Sets number of retries of a failed batch and delay before first retry
+    pub fn retry(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.config.max_retries = max_retries;
+        self.config.retry_backoff_ms = backoff.as_millis() as u64;
+        self
+    }
+
+    /// Adds header to every HTTP request, replacing default one of the same name, e.g. `User-Agent`
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.config
+            .http_config
+            .headers
+            .insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets hook adding headers to each HTTP request, see [`DataDogHeaderHook`]
+    pub fn header_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&HttpRequest) -> Vec<(String, String)> + Send + Sync + 'static,
+    {
+        self.config.http_config.header_hook = Some(DataDogHeaderHook::new(hook));
+        self
+    }
+
+    /// Sets maximum time flushing waits for queued logs to be sent
+    pub fn flush_timeout(mut self, timeout: Duration) -> Self {
+        self.config.flush_timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+
+    /// Enables or disables built-in redaction rules.
+    /// See [`DataDogRedactionConfig`](crate::config::DataDogRedactionConfig) for details.
+    pub fn redact_builtin(mut self, enable: bool) -> Self {
+        self.config.redaction.builtin_rules = enable;
+        self
+    }
+
+    /// Adds custom redaction rule. Invalid pattern makes building the logger fail.
+    pub fn redact(mut self, rule: DataDogRedactionRule) -> Self {
+        self.config.redaction.rules.push(rule);
+        self
+    }
+
+    /// Adds sampling rule. Rules are evaluated in order they were added.
+    /// Sample rate outside of `0..=1` range makes building the logger fail.
+    pub fn sample(mut self, rule: DataDogSamplingRule) -> Self {
+        self.config.sampling.push(rule);
+        self
+    }
+
+    /// Enables collapsing of repeated logs.
+    /// See [`DataDogDeduplicationConfig`](crate::config::DataDogDeduplicationConfig) for details.
+    pub fn deduplicate(mut self, deduplication: DataDogDeduplicationConfig) -> Self {
+        self.config.deduplication = Some(deduplication);
+        self
+    }
+
+    /// Sets level of logs accepted from `log` crate. Logs of all levels are accepted by default.
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.filters.set_default(level);
+        self
+    }
+
+    /// Sets level of logs accepted from `log` crate for given target and its submodules.
+    /// It takes precedence over [`level`](Self::level).
+    pub fn filter<S: Into<String>>(mut self, target: S, level: LevelFilter) -> Self {
+        self.filters.set_target(target.into(), level);
+        self
+    }
+
+    /// Additionally writes every log to a local sink, e.g. standard error or file.
+    /// Logs are written synchronously on the calling thread, independently of sending them to DataDog.
+    /// They are redacted with redaction rules of the logger first, see [`LocalSink`].
+    pub fn tee(mut self, sink: LocalSink) -> Self {
+        self.local_sink = Some(sink);
+        self
+    }
+
+    /// Adds processor run on every log before it is sent. Processors run in order they were added.
+    /// See [`LogProcessor`](crate::processor::LogProcessor) for details.
+    pub fn processor<P: LogProcessor + 'static>(mut self, processor: P) -> Self {
+        self.processors.push(Box::new(processor));
+        self
+    }
+
+    /// Uses HTTP(S) transport configured with `http_config`
+    pub fn http(self) -> DataDogLoggerBuilder<HttpDataDogClient> {
+        let builder = self.client_with(HttpDataDogClient::new);
+        #[cfg(any(feature = "with-async-std", feature = "with-smol"))]
+        let builder = DataDogLoggerBuilder {
+            unblocking: Some(Box::new(HttpDataDogClient::unblocking)),
+            ..builder
+        };
+        builder
+    }
+
+    /// Uses TCP transport configured with `tcp_config`. It is only available for blocking logger.
+    pub fn tcp(self) -> DataDogLoggerBuilder<TcpDataDogClient> {
+        self.client_with(TcpDataDogClient::new)
+    }
+
+    /// Sends logs to local DataDog agent configured with `agent_config`. No API key is needed.
+    pub fn agent(self) -> DataDogLoggerBuilder<AgentDataDogClient> {
+        self.client_with(AgentDataDogClient::new)
+    }
+
+    /// Sends logs to syslog relay configured with `syslog_config`
+    pub fn syslog(self) -> DataDogLoggerBuilder<SyslogDataDogClient> {
+        self.client_with(SyslogDataDogClient::new)
+    }
+
+    /// Writes logs to a file configured with `file_config` instead of sending them over network
+    pub fn file(self) -> DataDogLoggerBuilder<FileDataDogClient> {
+        self.client_with(FileDataDogClient::new)
+    }
+
+    /// Uses custom network client
+    pub fn client<T: 'static>(self, client: T) -> DataDogLoggerBuilder<T> {
+        self.client_with(move |_| Ok(client))
+    }
+
+    /// Uses custom network client created from final configuration of the logger
+    pub fn client_with<T, F>(self, factory: F) -> DataDogLoggerBuilder<T>
+    where
+        F: FnOnce(&DataDogConfig) -> Result<T, DataDogLoggerError> + 'static,
+    {
+        DataDogLoggerBuilder {
+            config: self.config,
+            client: Box::new(factory),
+            #[cfg(any(feature = "with-async-std", feature = "with-smol"))]
+            unblocking: None,
+            filters: self.filters,
+            local_sink: self.local_sink,
+            processors: self.processors,
+        }
+    }
+
+    /// Builds blocking logger. See [`DataDogLogger::blocking`](crate::logger::DataDogLogger::blocking).
+    pub fn build_blocking(self) -> Result<DataDogLogger, DataDogLoggerError>
+    where
+        C: DataDogClient + Send,
+    {
+        Redactor::validate(&self.config)?;
+        Sampler::validate(&self.config)?;
+        let client = (self.client)(&self.config)?;
+        Ok(
+            DataDogLogger::blocking_with_processors(client, self.config, self.processors)
+                .with_filters(self.filters)
+                .with_local_sink(self.local_sink),
+        )
+    }
+
+    /// Builds nonblocking logger. Returned future has to be spawned for logger to work.
+    /// See [`DataDogLogger::non_blocking_cold`](crate::logger::DataDogLogger::non_blocking_cold).
+    #[cfg(feature = "nonblocking-core")]
+    pub fn build_nonblocking(
+        self,
+    ) -> Result<(DataDogLogger, impl Future<Output = ()>), DataDogLoggerError>
+    where
+        C: AsyncDataDogClient,
+    {
+        Redactor::validate(&self.config)?;
+        Sampler::validate(&self.config)?;
+        let client = (self.client)(&self.config)?;
+        let (logger, future) =
+            DataDogLogger::non_blocking_cold_with_processors(client, self.config, self.processors);
+        Ok((
+            logger
+                .with_filters(self.filters)
+                .with_local_sink(self.local_sink),
+            future,
+        ))
+    }
+
+    /// Builds nonblocking logger and spawns its future to `tokio` runtime.
+    #[cfg(feature = "with-tokio")]
+    pub fn build_with_tokio(self) -> Result<DataDogLogger, DataDogLoggerError>
+    where
+        C: AsyncDataDogClient + Send,
+    {
+        let (logger, future) = self.build_nonblocking()?;
+        tokio::spawn(future);
+        Ok(logger)
+    }
+
+    /// Builds nonblocking logger and spawns its future to `async-std` runtime.
+    /// HTTP client sends requests on a thread pool, even if reqwest or hyper is enabled.
+    #[cfg(feature = "with-async-std")]
+    pub fn build_with_async_std(self) -> Result<DataDogLogger, DataDogLoggerError>
+    where
+        C: AsyncDataDogClient + Send,
+    {
+        let (logger, future) = self.without_tokio().build_nonblocking()?;
+        async_std::task::spawn(future);
+        Ok(logger)
+    }
+
+    /// Builds nonblocking logger and spawns its future to global `smol` executor.
+    /// HTTP client sends requests on a thread pool, even if reqwest or hyper is enabled.
+    #[cfg(feature = "with-smol")]
+    pub fn build_with_smol(self) -> Result<DataDogLogger, DataDogLoggerError>
+    where
+        C: AsyncDataDogClient + Send,
+    {
+        let (logger, future) = self.without_tokio().build_nonblocking()?;
+        smol::spawn(future).detach();
+        Ok(logger)
+    }
+
+    /// Replaces HTTP client with one that does not need tokio
+    #[cfg(any(feature = "with-async-std", feature = "with-smol"))]
+    fn without_tokio(mut self) -> Self {
+        if let Some(factory) = self.unblocking.take() {
+            self.client = factory;
+        }
+        self
+    }
+
+    /// Builds blocking logger and sets it as logger of `log` crate.
+    pub fn install_blocking(self) -> Result<DataDogLoggerGuard, DataDogLoggerError>
+    where
+        C: DataDogClient + Send,
+    {
+        let max_level = self.filters.max_level();
+        install(self.build_blocking()?, max_level)
+    }
+
+    /// Builds nonblocking logger and sets it as logger of `log` crate.
+    /// Returned future has to be spawned for logger to work.
+    #[cfg(feature = "nonblocking-core")]
+    pub fn install_nonblocking(
+        self,
+    ) -> Result<(DataDogLoggerGuard, impl Future<Output = ()>), DataDogLoggerError>
+    where
+        C: AsyncDataDogClient,
+    {
+        let max_level = self.filters.max_level();
+        let (logger, future) = self.build_nonblocking()?;
+        Ok((install(logger, max_level)?, future))
+    }
+
+    /// Builds nonblocking logger, spawns its future to `tokio` runtime and sets it as logger of `log` crate.
+    #[cfg(feature = "with-tokio")]
+    pub fn install_with_tokio(self) -> Result<DataDogLoggerGuard, DataDogLoggerError>
+    where
+        C: AsyncDataDogClient + Send,
+    {
+        let max_level = self.filters.max_level();
+        install(self.build_with_tokio()?, max_level)
+    }
+
+    /// Builds nonblocking logger, spawns its future to `async-std` runtime and sets it as logger of `log` crate.
+    #[cfg(feature = "with-async-std")]
+    pub fn install_with_async_std(self) -> Result<DataDogLoggerGuard, DataDogLoggerError>
+    where
+        C: AsyncDataDogClient + Send,
+    {
+        let max_level = self.filters.max_level();
+        install(self.build_with_async_std()?, max_level)
+    }
+
+    /// Builds nonblocking logger, spawns its future to global `smol` executor and sets it as logger of `log` crate.
+    #[cfg(feature = "with-smol")]
+    pub fn install_with_smol(self) -> Result<DataDogLoggerGuard, DataDogLoggerError>
+    where
+        C: AsyncDataDogClient + Send,
+    {
+        let max_level = self.filters.max_level();
+        install(self.build_with_smol()?, max_level)
+    }
+}
+
+fn install(
+    logger: DataDogLogger,
+    max_level: LevelFilter,
+) -> Result<DataDogLoggerGuard, DataDogLoggerError> {
+    log::set_boxed_logger(Box::new(logger))?;
+    log::set_max_level(max_level);
+    Ok(DataDogLoggerGuard { _private: () })
+}
+
+/// Guard of logger installed into `log` crate.
+///
+/// Logger set in `log` crate is never dropped, hence logs still queued when program exits would be lost.
+/// Dropping this guard flushes the logger, so it should be kept alive until the end of `main`.
+#[derive(Debug)]
+#[must_use = "logger is flushed when guard is dropped"]
+pub struct DataDogLoggerGuard {
+    _private: (),
+}
+
+impl Drop for DataDogLoggerGuard {
+    fn drop(&mut self) {
+        log::logger().flush();
+    }
+}
+
+/// Creates [`DataDogLoggerBuilder`](crate::logger::DataDogLoggerBuilder) with version of the calling crate.
+///
+/// Version is taken from `DD_VERSION` environment variable if it is set, otherwise from `CARGO_PKG_VERSION` of the crate invoking the macro.
+/// Both can be overriden by calling [`version`](crate::logger::DataDogLoggerBuilder::version).
+///
+/// # Examples
+///```rust
+///let logger = datadog_logs::builder!()
+///    .service("my-service")
+///    .env("prod")
+///    .build_blocking()
+///    .unwrap();
+///```
+#[macro_export]
+macro_rules! builder {
+    () => {
+        $crate::logger::DataDogLogger::builder().version(
+            ::std::env::var("DD_VERSION")
+                .unwrap_or_else(|_| ::std::string::String::from(::std::env!("CARGO_PKG_VERSION"))),
+        )
+    };
+}
@@ -0,0 +1,147 @@
+use serde_json::{Map, Value};
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+thread_local! {
+    // scopes entered on current thread, innermost last
+    static SCOPES: RefCell<Vec<Map<String, Value>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Runs closure with fields attached to every log emitted inside it on current thread.
+///
+/// Scopes can be nested. Fields of inner scopes take precedence over fields of outer ones
+/// and attributes passed explicitly with a log take precedence over all of them.
+///
+/// # Examples
+///```rust
+///use datadog_logs::logger::{with_context, DataDogLogger, DataDogLogLevel};
+///
+///let logger = DataDogLogger::builder().build_blocking().unwrap();
+///
+///with_context([("request_id", "42"), ("tenant", "acme")], || {
+///    // sent with `request_id` and `tenant` attributes
+///    logger.log("Request handled", DataDogLogLevel::Informational);
+///});
+///```
+pub fn with_context<I, K, V, F, R>(fields: I, f: F) -> R
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<Value>,
+    F: FnOnce() -> R,
+{
+    let _scope = Scope::enter(collect(fields));
+    f()
+}
+
+/// Returns fields of all scopes entered on current thread, e.g. to pass them to a spawned task or thread
+pub fn current_context() -> Map<String, Value> {
+    let mut fields = Map::new();
+    merge_context(&mut fields);
+    fields
+}
+
+/// Inserts fields of current scopes into attributes, keeping values already present
+pub(crate) fn merge_context(attributes: &mut Map<String, Value>) {
+    SCOPES.with(|scopes| {
+        for scope in scopes.borrow().iter().rev() {
+            for (key, value) in scope {
+                if !attributes.contains_key(key) {
+                    attributes.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    });
+}
+
+fn collect<I, K, V>(fields: I) -> Map<String, Value>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<Value>,
+{
+    fields
+        .into_iter()
+        .map(|(key, value)| (key.into(), value.into()))
+        .collect()
+}
+
+// leaves the scope also when closure or future panics
+struct Scope;
+
+impl Scope {
+    fn enter(fields: Map<String, Value>) -> Self {
+        SCOPES.with(|scopes| scopes.borrow_mut().push(fields));
+        Scope
+    }
+
+    fn leave(self) -> Map<String, Value> {
+        let fields = SCOPES.with(|scopes| scopes.borrow_mut().pop());
+        std::mem::forget(self);
+        fields.unwrap_or_default()
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        SCOPES.with(|scopes| scopes.borrow_mut().pop());
+    }
+}
+
+/// Future with fields attached to every log emitted while it is polled.
+///
+/// Fields follow the future across threads of multi-threaded executors, as they are attached on every poll.
+/// It is created with [`WithContextExt::with_context`].
+#[derive(Debug)]
+pub struct WithContext<F> {
+    future: Pin<Box<F>>,
+    fields: Option<Map<String, Value>>,
+}
+
+impl<F: Future> Future for WithContext<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let scope = Scope::enter(self.fields.take().unwrap_or_default());
+        let poll = self.future.as_mut().poll(cx);
+        self.fields = Some(scope.leave());
+        poll
+    }
+}
+
+/// Attaches context fields to futures, see [`with_context`] for details
+///
+/// # Examples
+///```rust
+///use datadog_logs::logger::{DataDogLogger, DataDogLogLevel, WithContextExt};
+///
+///# async fn func() {
+///let logger = DataDogLogger::builder().build_blocking().unwrap();
+///
+///async {
+///    logger.log("Request handled", DataDogLogLevel::Informational);
+///}
+///.with_context([("request_id", "42")])
+///.await;
+///# }
+///```
+pub trait WithContextExt: Future + Sized {
+    /// Attaches fields to every log emitted while this future is polled
+    fn with_context<I, K, V>(self, fields: I) -> WithContext<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        WithContext {
+            future: Box::pin(self),
+            fields: Some(collect(fields)),
+        }
+    }
+}
+
+impl<F: Future> WithContextExt for F {}
@@ -0,0 +1,139 @@
+use super::{log::DataDogLog, timestamp::rfc3339};
+use crate::config::DataDogDeduplicationConfig;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Collapses logs with the same key fields received within a window
+#[derive(Debug)]
+pub(crate) struct Deduplicator {
+    window: Duration,
+    key_fields: Vec<String>,
+    pending: HashMap<String, Pending>,
+    // windows always have the same length, so keys are ordered by their deadlines
+    deadlines: VecDeque<(Instant, String)>,
+}
+
+#[derive(Debug)]
+struct Pending {
+    log: DataDogLog,
+    count: u64,
+    first: SystemTime,
+    last: SystemTime,
+}
+
+impl Deduplicator {
+    pub(crate) fn new(config: &DataDogDeduplicationConfig) -> Self {
+        Deduplicator {
+            window: Duration::from_millis(config.window_ms),
+            key_fields: config.key_fields.clone(),
+            pending: HashMap::new(),
+            deadlines: VecDeque::new(),
+        }
+    }
+
+    /// Stores log until its window ends, unless the same log is already stored
+    pub(crate) fn process(&mut self, log: DataDogLog) {
+        let key = self.key(&log);
+        let now = SystemTime::now();
+        match self.pending.get_mut(&key) {
+            Some(pending) => {
+                pending.count += 1;
+                pending.last = now;
+            }
+            None => {
+                self.deadlines
+                    .push_back((Instant::now() + self.window, key.clone()));
+                self.pending.insert(
+                    key,
+                    Pending {
+                        log,
+                        count: 1,
+                        first: now,
+                        last: now,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Moves logs whose window ended before `now` to output
+    pub(crate) fn expire(&mut self, now: Instant, output: &mut Vec<DataDogLog>) {
+        while let Some((deadline, _)) = self.deadlines.front() {
+            if *deadline > now {
+                break;
+            }
+            if let Some((_, key)) = self.deadlines.pop_front() {
+                if let Some(pending) = self.pending.remove(&key) {
+                    output.push(pending.into_log());
+                }
+            }
+        }
+    }
+
+    /// Moves all stored logs to output regardless of their windows
+    pub(crate) fn drain(&mut self, output: &mut Vec<DataDogLog>) {
+        for (_, key) in self.deadlines.drain(..) {
+            if let Some(pending) = self.pending.remove(&key) {
+                output.push(pending.into_log());
+            }
+        }
+    }
+
+    /// Time when the oldest stored log has to be sent
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.deadlines.front().map(|(deadline, _)| *deadline)
+    }
+
+    fn key(&self, log: &DataDogLog) -> String {
+        let mut key = String::new();
+        for field in &self.key_fields {
+            match field.as_str() {
+                "message" => key.push_str(&log.message),
+                "level" => key.push_str(&log.level),
+                "service" => key.push_str(&log.service),
+                "host" => key.push_str(&log.host),
+                "source" => key.push_str(&log.ddsource),
+                "tags" => key.push_str(&log.ddtags.to_string()),
+                "target" => push_attribute(&mut key, log, "logger.name"),
+                path => push_attribute(&mut key, log, path),
+            }
+            // separator that is unlikely to appear in logs
+            key.push('\u{1f}');
+        }
+        key
+    }
+}
+
+impl Pending {
+    fn into_log(self) -> DataDogLog {
+        let mut log = self.log;
+        if self.count > 1 {
+            log.attributes
+                .insert("repeat_count".into(), self.count.into());
+            log.attributes
+                .insert("first_timestamp".into(), rfc3339(self.first).into());
+            log.attributes
+                .insert("last_timestamp".into(), rfc3339(self.last).into());
+        }
+        log
+    }
+}
+
+fn push_attribute(key: &mut String, log: &DataDogLog, path: &str) {
+    let mut segments = path.split('.');
+    let mut value = segments
+        .next()
+        .and_then(|segment| log.attributes.get(segment));
+    for segment in segments {
+        value = value.and_then(|value| value.get(segment));
+    }
+
+    match value {
+        Some(Value::String(value)) => key.push_str(value),
+        Some(value) => key.push_str(&value.to_string()),
+        None => {}
+    }
+}
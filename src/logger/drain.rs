@@ -0,0 +1,140 @@
+use super::{level::DataDogLogLevel, logger::DataDogLogger};
+use serde_json::{Map, Value};
+use slog::{Drain, FlushError, Key, Level, Never, OwnedKVList, Record, Serializer, KV};
+use std::fmt::Arguments;
+use std::panic::AssertUnwindSafe;
+
+/// `slog` drain sending records to DataDog
+///
+/// It wraps [`DataDogLogger`](crate::logger::DataDogLogger), so records are sent by its thread or task.
+/// Since logging does not involve any IO on the calling thread, there is no need to wrap this drain in `slog_async::Async`.
+///
+/// Key-value pairs of both the record and the logger are sent as attributes of the log.
+/// In case of duplicated keys, record values take precedence over logger values.
+///
+/// # Examples
+///```rust
+///use datadog_logs::logger::{DataDogDrain, DataDogLogger};
+///use slog::{info, o, Drain, Logger};
+///
+///let logger = DataDogLogger::builder().build_blocking().unwrap();
+///let root = Logger::root(DataDogDrain::new(logger).fuse(), o!("version" => "1.0"));
+///
+///info!(root, "Request handled"; "status" => 200);
+///```
+#[derive(Debug)]
+pub struct DataDogDrain {
+    // logger only hands messages over to channels, so a panic cannot leave it in inconsistent state
+    logger: AssertUnwindSafe<DataDogLogger>,
+}
+
+impl DataDogDrain {
+    /// Creates new drain sending records with given logger
+    pub fn new(logger: DataDogLogger) -> Self {
+        DataDogDrain {
+            logger: AssertUnwindSafe(logger),
+        }
+    }
+
+    /// Exposes underlying logger e.g. to read its self log
+    pub fn logger(&self) -> &DataDogLogger {
+        &self.logger
+    }
+}
+
+impl From<DataDogLogger> for DataDogDrain {
+    fn from(logger: DataDogLogger) -> Self {
+        DataDogDrain::new(logger)
+    }
+}
+
+impl Drain for DataDogDrain {
+    type Ok = ();
+    type Err = Never;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let level = match record.level() {
+            Level::Critical => DataDogLogLevel::Critical,
+            Level::Error => DataDogLogLevel::Error,
+            Level::Warning => DataDogLogLevel::Warning,
+            Level::Info => DataDogLogLevel::Informational,
+            Level::Debug | Level::Trace => DataDogLogLevel::Debug,
+        };
+
+        // first value written for a key wins, so record values go before logger values
+        let mut serializer = AttributesSerializer(Map::new());
+        record
+            .kv()
+            .serialize(record, &mut serializer)
+            .unwrap_or_default();
+        values
+            .serialize(record, &mut serializer)
+            .unwrap_or_default();
+
+        self.logger.log_record(
+            record.msg(),
+            level,
+            Some(record.module()),
+            serializer.0,
+            None,
+        );
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        log::Log::flush(&*self.logger);
+        Ok(())
+    }
+}
+
+struct AttributesSerializer(Map<String, Value>);
+
+impl AttributesSerializer {
+    fn insert<T: Into<Value>>(&mut self, key: Key, value: T) -> slog::Result {
+        self.0
+            .entry(key.to_string())
+            .or_insert_with(|| value.into());
+        Ok(())
+    }
+}
+
+macro_rules! emit_as_value {
+    ($($method:ident: $ty:ty),*) => {
+        $(
+            fn $method(&mut self, key: Key, value: $ty) -> slog::Result {
+                self.insert(key, value)
+            }
+        )*
+    };
+}
+
+impl Serializer for AttributesSerializer {
+    emit_as_value!(
+        emit_usize: usize,
+        emit_isize: isize,
+        emit_bool: bool,
+        emit_u8: u8,
+        emit_i8: i8,
+        emit_u16: u16,
+        emit_i16: i16,
+        emit_u32: u32,
+        emit_i32: i32,
+        emit_u64: u64,
+        emit_i64: i64,
+        emit_f32: f32,
+        emit_f64: f64,
+        emit_str: &str
+    );
+
+    fn emit_unit(&mut self, key: Key) -> slog::Result {
+        self.insert(key, Value::Null)
+    }
+
+    fn emit_none(&mut self, key: Key) -> slog::Result {
+        self.insert(key, Value::Null)
+    }
+
+    fn emit_arguments(&mut self, key: Key, value: &Arguments) -> slog::Result {
+        self.insert(key, value.to_string())
+    }
+}
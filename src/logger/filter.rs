@@ -0,0 +1,58 @@
+use log::{Level, LevelFilter};
+
+/// Level filters applied to logs coming from `log` crate
+#[derive(Debug, Clone)]
+pub(crate) struct LevelFilters {
+    default: LevelFilter,
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Default for LevelFilters {
+    fn default() -> Self {
+        LevelFilters {
+            default: LevelFilter::Trace,
+            targets: Vec::new(),
+        }
+    }
+}
+
+impl LevelFilters {
+    /// Sets level for targets that do not match any of target specific filters
+    pub(crate) fn set_default(&mut self, level: LevelFilter) {
+        self.default = level;
+    }
+
+    /// Sets level for a target and all of its submodules
+    pub(crate) fn set_target(&mut self, target: String, level: LevelFilter) {
+        self.targets.retain(|(t, _)| *t != target);
+        self.targets.push((target, level));
+    }
+
+    /// Checks if log with given target and level passes filters.
+    ///
+    /// The most specific (longest) matching target filter wins.
+    pub(crate) fn enabled(&self, target: &str, level: Level) -> bool {
+        let filter = self
+            .targets
+            .iter()
+            .filter(|(prefix, _)| matches_target(prefix, target))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, filter)| *filter)
+            .unwrap_or(self.default);
+
+        level <= filter
+    }
+
+    /// Most verbose level allowed by any of filters
+    pub(crate) fn max_level(&self) -> LevelFilter {
+        self.targets
+            .iter()
+            .map(|(_, filter)| *filter)
+            .fold(self.default, std::cmp::max)
+    }
+}
+
+/// Checks if target is equal to prefix or is its submodule
+pub(crate) fn matches_target(prefix: &str, target: &str) -> bool {
+    target == prefix || (target.starts_with(prefix) && target[prefix.len()..].starts_with("::"))
+}
@@ -1,7 +1,11 @@
-use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
 
 /// Logging levels according to SysLog
-#[derive(Debug)]
+///
+/// Levels are ordered from the most severe, so `Emergency < Debug`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DataDogLogLevel {
     /// Emergency level
     Emergency,
@@ -35,3 +39,42 @@ impl Display for DataDogLogLevel {
         }
     }
 }
+
+impl DataDogLogLevel {
+    /// Returns syslog severity of the level, from 0 for `Emergency` to 7 for `Debug`
+    pub fn severity(self) -> u8 {
+        self as u8
+    }
+
+    /// Maps level to the closest level of `log` crate
+    pub(crate) fn to_log_level(self) -> log::Level {
+        match self {
+            DataDogLogLevel::Emergency
+            | DataDogLogLevel::Alert
+            | DataDogLogLevel::Critical
+            | DataDogLogLevel::Error => log::Level::Error,
+            DataDogLogLevel::Warning => log::Level::Warn,
+            DataDogLogLevel::Notice | DataDogLogLevel::Informational => log::Level::Info,
+            DataDogLogLevel::Debug => log::Level::Debug,
+        }
+    }
+}
+
+impl FromStr for DataDogLogLevel {
+    type Err = String;
+
+    /// Parses level from its DataDog status, e.g. `err`, or its full name, e.g. `error`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "emerg" | "emergency" => Ok(DataDogLogLevel::Emergency),
+            "alert" => Ok(DataDogLogLevel::Alert),
+            "crit" | "critical" => Ok(DataDogLogLevel::Critical),
+            "err" | "error" => Ok(DataDogLogLevel::Error),
+            "warning" | "warn" => Ok(DataDogLogLevel::Warning),
+            "notice" => Ok(DataDogLogLevel::Notice),
+            "info" | "informational" => Ok(DataDogLogLevel::Informational),
+            "debug" => Ok(DataDogLogLevel::Debug),
+            _ => Err(format!("Unknown log level : {}", s)),
+        }
+    }
+}
@@ -1,18 +1,106 @@
-use serde::{Deserialize, Serialize};
+use super::{level::DataDogLogLevel, tags::Tags};
+use crate::config::DataDogConfig;
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::{fmt::Display, sync::Arc};
 
 /// Information passed to DataDog
+///
+/// Fields that are the same for every log of a logger are shared between logs instead of being copied into each of them.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct DataDogLog {
     /// The message
     pub message: String,
-    /// Message tags
-    pub ddtags: Option<String>,
+    /// Message tags, serialized as a comma separated string
+    #[serde(default, skip_serializing_if = "Tags::is_empty")]
+    pub ddtags: Tags,
     /// Message source
-    pub ddsource: String,
+    pub ddsource: Arc<str>,
     /// Host that sent the message
-    pub host: String,
+    pub host: Arc<str>,
     /// Service that sent the message
-    pub service: String,
+    pub service: Arc<str>,
+    /// Environment of the service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<Arc<str>>,
+    /// Version of the service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<Arc<str>>,
     /// Datadog understandable string indicating level
     pub level: String,
+    /// Additional attributes of the message.
+    /// They are serialized as top level fields of the log, thus they can be used as facets in DataDog.
+    /// Attributes named like one of the fields above are serialized with `attr.` prefix, e.g. `attr.host`,
+    /// so that they do not clash with them.
+    #[serde(flatten, serialize_with = "serialize_attributes")]
+    pub attributes: Map<String, Value>,
+}
+
+/// Names of top level fields of [`DataDogLog`] that attributes must not override
+const RESERVED: [&str; 8] = [
+    "message", "ddtags", "ddsource", "host", "service", "env", "version", "level",
+];
+
+fn serialize_attributes<S: Serializer>(
+    attributes: &Map<String, Value>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(attributes.len()))?;
+    for (key, value) in attributes {
+        if !RESERVED.contains(&key.as_str()) {
+            map.serialize_entry(key, value)?;
+            continue;
+        }
+        let prefixed = format!("attr.{}", key);
+        // attribute explicitly named with the prefix wins
+        if !attributes.contains_key(&prefixed) {
+            map.serialize_entry(&prefixed, value)?;
+        }
+    }
+    map.end()
+}
+
+/// Fields of [`DataDogLog`] taken from configuration, created once per logger
+#[derive(Debug, Clone)]
+pub(crate) struct StaticFields {
+    tags: Tags,
+    source: Arc<str>,
+    host: Arc<str>,
+    service: Arc<str>,
+    env: Option<Arc<str>>,
+    version: Option<Arc<str>>,
+}
+
+impl StaticFields {
+    pub(crate) fn new(config: &DataDogConfig) -> Self {
+        StaticFields {
+            tags: config.tags.clone(),
+            source: config.source.as_str().into(),
+            host: config.hostname.as_deref().unwrap_or_default().into(),
+            service: config.service.as_deref().unwrap_or_default().into(),
+            env: config.env.as_deref().map(Arc::from),
+            version: config.version.as_deref().map(Arc::from),
+        }
+    }
+}
+
+impl DataDogLog {
+    pub(crate) fn new<T: Display>(
+        fields: &StaticFields,
+        message: T,
+        level: DataDogLogLevel,
+        attributes: Map<String, Value>,
+    ) -> Self {
+        DataDogLog {
+            message: message.to_string(),
+            ddtags: fields.tags.clone(),
+            service: fields.service.clone(),
+            host: fields.host.clone(),
+            env: fields.env.clone(),
+            version: fields.version.clone(),
+            ddsource: fields.source.clone(),
+            level: level.to_string(),
+            attributes,
+        }
+    }
 }
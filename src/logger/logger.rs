@@ -1,27 +1,66 @@
 use super::blocking;
-#[cfg(feature = "nonblocking")]
+#[cfg(feature = "nonblocking-core")]
 use super::nonblocking;
-use super::{level::DataDogLogLevel, log::DataDogLog};
-#[cfg(feature = "nonblocking")]
+use super::{
+    builder::DataDogLoggerBuilder,
+    context,
+    filter::LevelFilters,
+    level::DataDogLogLevel,
+    log::{DataDogLog, StaticFields},
+    message::LoggerMessage,
+    panic_hook,
+    redaction::Redactor,
+    sampling::{Sampler, Sampling},
+    tags::Tags,
+    tee::LocalSink,
+};
+#[cfg(feature = "nonblocking-core")]
 use crate::client::AsyncDataDogClient;
-use crate::{client::DataDogClient, config::DataDogConfig, error::DataDogLoggerError};
+use crate::{
+    client::DataDogClient, config::DataDogConfig, error::DataDogLoggerError,
+    processor::LogProcessor,
+};
 use flume::{bounded, unbounded, Receiver, Sender};
-#[cfg(feature = "nonblocking")]
+#[cfg(feature = "nonblocking-core")]
 use futures::Future;
 use log::{LevelFilter, Log, Metadata, Record};
-use std::{fmt::Display, ops::Drop, thread};
+use serde_json::{Map, Value};
+use std::{fmt::Display, ops::Drop, sync::Arc, thread, time::Instant};
 
 #[derive(Debug)]
 /// Logger that logs directly to DataDog via HTTP(S)
 pub struct DataDogLogger {
     config: DataDogConfig,
-    logsender: Option<Sender<DataDogLog>>,
+    logsender: Option<Sender<LoggerMessage>>,
     selflogrv: Option<Receiver<String>>,
     selflogsd: Option<Sender<String>>,
     logger_handle: Option<thread::JoinHandle<()>>,
+    filters: LevelFilters,
+    local_sink: Option<LocalSink>,
+    redactor: Option<Arc<Redactor>>,
+    sampler: Sampler,
+    fields: StaticFields,
 }
 
 impl DataDogLogger {
+    /// Creates a builder that configures transport, mode and filters of the logger in one place.
+    ///
+    /// # Examples
+    ///```rust
+    ///use datadog_logs::logger::DataDogLogger;
+    ///use log::LevelFilter;
+    ///
+    ///let logger = DataDogLogger::builder()
+    ///    .apikey("<api key>")
+    ///    .service("my-service")
+    ///    .level(LevelFilter::Info)
+    ///    .build_blocking()
+    ///    .unwrap();
+    ///```
+    pub fn builder() -> DataDogLoggerBuilder {
+        DataDogLoggerBuilder::new()
+    }
+
     /// Exposes self log of the logger.
     ///
     /// Contains diagnostic messages with details of errors occuring inside logger.
@@ -30,6 +69,11 @@ impl DataDogLogger {
         &self.selflogrv
     }
 
+    /// Exposes configuration of the logger, including settings taken from environment
+    pub fn config(&self) -> &DataDogConfig {
+        &self.config
+    }
+
     /// Creates new blocking DataDogLogger instance
     ///
     /// What it means is that no executor is used to host DataDog network client. A new thread is started instead.
@@ -48,6 +92,18 @@ impl DataDogLogger {
     where
         T: DataDogClient + Send + 'static,
     {
+        Self::blocking_with_processors(client, config, Vec::new())
+    }
+
+    pub(crate) fn blocking_with_processors<T>(
+        client: T,
+        config: DataDogConfig,
+        processors: Vec<Box<dyn LogProcessor>>,
+    ) -> Self
+    where
+        T: DataDogClient + Send + 'static,
+    {
+        let config = config.resolve();
         let (slsender, slreceiver) = if config.enable_self_log {
             let (s, r) = bounded::<String>(100);
             (Some(s), Some(r))
@@ -60,15 +116,32 @@ impl DataDogLogger {
             None => unbounded(),
         };
 
-        let logger_handle =
-            thread::spawn(move || blocking::logger_thread(client, receiver, slsender));
+        let redactor = Redactor::shared(&config, &slsender);
+
+        let thread_config = config.clone();
+        let thread_redactor = redactor.clone();
+        let logger_handle = thread::spawn(move || {
+            blocking::logger_thread(
+                client,
+                receiver,
+                slsender,
+                thread_config,
+                processors,
+                thread_redactor,
+            )
+        });
 
         DataDogLogger {
+            sampler: Sampler::new(&config),
+            fields: StaticFields::new(&config),
             config,
             logsender: Some(sender),
             selflogrv: slreceiver,
             selflogsd: slogsender_clone,
             logger_handle: Some(logger_handle),
+            filters: LevelFilters::default(),
+            local_sink: None,
+            redactor,
         }
     }
 
@@ -87,6 +160,34 @@ impl DataDogLogger {
         logger
     }
 
+    /// Creates new non-blocking `DataDogLogger` instance
+    ///
+    /// Internally spawns logger future to `async-std` runtime.
+    /// It is equivalent to calling [`non_blocking_cold`](Self::non_blocking_cold) and spawning future with `async_std::task::spawn`.
+    #[cfg(feature = "with-async-std")]
+    pub fn non_blocking_with_async_std<T>(client: T, config: DataDogConfig) -> Self
+    where
+        T: AsyncDataDogClient + Send + 'static,
+    {
+        let (logger, future) = Self::non_blocking_cold(client, config);
+        async_std::task::spawn(future);
+        logger
+    }
+
+    /// Creates new non-blocking `DataDogLogger` instance
+    ///
+    /// Internally spawns logger future to global `smol` executor.
+    /// It is equivalent to calling [`non_blocking_cold`](Self::non_blocking_cold) and spawning future with `smol::spawn`.
+    #[cfg(feature = "with-smol")]
+    pub fn non_blocking_with_smol<T>(client: T, config: DataDogConfig) -> Self
+    where
+        T: AsyncDataDogClient + Send + 'static,
+    {
+        let (logger, future) = Self::non_blocking_cold(client, config);
+        smol::spawn(future).detach();
+        logger
+    }
+
     /// Creates new non-blocking `DataDogLogger` instance
     ///
     /// What it means is that logger requires executor to run. This executor will host a task that will receive messages to log.
@@ -107,7 +208,7 @@ impl DataDogLogger {
     ///tokio::spawn(future);
     ///# }
     ///```
-    #[cfg(feature = "nonblocking")]
+    #[cfg(feature = "nonblocking-core")]
     pub fn non_blocking_cold<T>(
         client: T,
         config: DataDogConfig,
@@ -115,6 +216,19 @@ impl DataDogLogger {
     where
         T: AsyncDataDogClient,
     {
+        Self::non_blocking_cold_with_processors(client, config, Vec::new())
+    }
+
+    #[cfg(feature = "nonblocking-core")]
+    pub(crate) fn non_blocking_cold_with_processors<T>(
+        client: T,
+        config: DataDogConfig,
+        processors: Vec<Box<dyn LogProcessor>>,
+    ) -> (Self, impl Future<Output = ()>)
+    where
+        T: AsyncDataDogClient,
+    {
+        let config = config.resolve();
         let (slsender, slreceiver) = if config.enable_self_log {
             let (s, r) = bounded::<String>(100);
             (Some(s), Some(r))
@@ -126,14 +240,27 @@ impl DataDogLogger {
             Some(capacity) => bounded(capacity),
             None => unbounded(),
         };
-        let logger_future = nonblocking::logger_future(client, logreceiver, slsender);
+        let redactor = Redactor::shared(&config, &slsender);
+        let logger_future = nonblocking::logger_future(
+            client,
+            logreceiver,
+            slsender,
+            config.clone(),
+            processors,
+            redactor.clone(),
+        );
 
         let logger = DataDogLogger {
+            sampler: Sampler::new(&config),
+            fields: StaticFields::new(&config),
             config,
             logsender: Some(logsender),
             selflogrv: slreceiver,
             selflogsd: slogsender_clone,
             logger_handle: None,
+            filters: LevelFilters::default(),
+            local_sink: None,
+            redactor,
         };
 
         (logger, logger_future)
@@ -144,6 +271,8 @@ impl DataDogLogger {
     /// This function does not invoke any IO operation by itself. Instead it sends messages to logger thread or task using channels.
     /// Therefore it is quite lightweight.
     ///
+    /// Fields of current context scopes are sent as attributes, see [`with_context`](crate::logger::with_context).
+    ///
     /// ## Examples
     ///
     ///```rust
@@ -156,17 +285,78 @@ impl DataDogLogger {
     ///logger.log("message", DataDogLogLevel::Error);
     ///```
     pub fn log<T: Display>(&self, message: T, level: DataDogLogLevel) {
-        let log = DataDogLog {
-            message: message.to_string(),
-            ddtags: self.config.tags.clone(),
-            service: self.config.service.clone().unwrap_or_default(),
-            host: self.config.hostname.clone().unwrap_or_default(),
-            ddsource: self.config.source.clone(),
-            level: level.to_string(),
-        };
+        self.log_record(message, level, None, Map::new(), None);
+    }
+
+    /// Sends log with additional tags to DataDog thread or task.
+    ///
+    /// Tags are added to tags from [`DataDogConfig`](crate::config::DataDogConfig), duplicates are sent once.
+    ///
+    /// ## Examples
+    ///
+    ///```rust
+    ///use datadog_logs::logger::{DataDogLogger, DataDogLogLevel, Tags};
+    ///
+    ///let logger = DataDogLogger::builder().build_blocking().unwrap();
+    ///let tags = Tags::from_pairs([("order", "1234")]).unwrap();
+    ///
+    ///logger.log_with("Order shipped", DataDogLogLevel::Informational, &tags);
+    ///```
+    pub fn log_with<T: Display>(&self, message: T, level: DataDogLogLevel, tags: &Tags) {
+        self.log_record(message, level, None, Map::new(), Some(tags));
+    }
+
+    /// Logs message with target it originates from and attributes.
+    /// Target is sent as `logger.name` attribute.
+    /// Fields of current context scopes are added to attributes, see [`with_context`](crate::logger::with_context).
+    ///
+    /// Sampling happens before log is created, so that dropped logs are cheap.
+    /// Local sink receives all logs, including those dropped by sampling.
+    pub(crate) fn log_record<T: Display>(
+        &self,
+        message: T,
+        level: DataDogLogLevel,
+        target: Option<&str>,
+        mut attributes: Map<String, Value>,
+        tags: Option<&Tags>,
+    ) {
+        context::merge_context(&mut attributes);
+        let sampling = self.sampler.sample(level, target, &attributes);
+        if sampling == Sampling::Drop && self.local_sink.is_none() {
+            return;
+        }
+        if let Sampling::Keep(Some(sample_rate)) = sampling {
+            attributes.insert("sample_rate".into(), sample_rate.into());
+        }
+        if let Some(target) = target {
+            // `logger.name` is DataDog standard attribute for the name of the logger
+            if let Value::Object(logger) = attributes
+                .entry("logger")
+                .or_insert_with(|| Value::Object(Map::new()))
+            {
+                logger.entry("name").or_insert_with(|| Value::from(target));
+            }
+        }
+
+        let mut log = DataDogLog::new(&self.fields, message, level, attributes);
+        if let Some(tags) = tags {
+            log.ddtags.extend(tags);
+        }
+
+        if let Some(ref sink) = self.local_sink {
+            if let Err(e) = sink.write(&log) {
+                if let Some(ref selflog) = self.selflogsd {
+                    selflog.try_send(e.to_string()).unwrap_or_default();
+                }
+            }
+        }
+
+        if sampling == Sampling::Drop {
+            return;
+        }
 
         if let Some(ref sender) = self.logsender {
-            match sender.try_send(log) {
+            match sender.try_send(LoggerMessage::Log(log)) {
                 Ok(()) => {
                     // nothing
                 }
@@ -227,7 +417,7 @@ impl DataDogLogger {
     ///warn!("A warning");
     ///# }
     ///```
-    #[cfg(feature = "nonblocking")]
+    #[cfg(feature = "nonblocking-core")]
     pub fn set_nonblocking_logger<T>(
         client: T,
         config: DataDogConfig,
@@ -241,14 +431,50 @@ impl DataDogLogger {
         log::set_max_level(level);
         Ok(future)
     }
+
+    /// Installs panic hook that logs panics before the process dies.
+    ///
+    /// Panic message, location and thread are logged at `Critical` level along with backtrace.
+    /// They are also available as `error.kind`, `error.message`, `error.stack` and `logger.thread_name` attributes.
+    /// Hook waits until the log is sent, but no longer than `flush_timeout_ms` from config.
+    /// Afterwards it calls previously installed hook.
+    ///
+    /// Hook does not keep the logger alive. Panics occurring after the logger is dropped are not logged.
+    ///
+    /// # Examples
+    ///```rust
+    ///use datadog_logs::logger::DataDogLogger;
+    ///
+    ///let logger = DataDogLogger::builder().build_blocking().unwrap();
+    ///logger.install_panic_hook();
+    ///```
+    pub fn install_panic_hook(&self) {
+        if let Some(ref sender) = self.logsender {
+            panic_hook::install(self.config.clone(), sender.downgrade());
+        }
+    }
+
+    pub(crate) fn with_filters(mut self, filters: LevelFilters) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    pub(crate) fn with_local_sink(mut self, local_sink: Option<LocalSink>) -> Self {
+        self.local_sink = local_sink.map(|sink| sink.with_redactor(self.redactor.clone()));
+        self
+    }
 }
 
 impl Log for DataDogLogger {
-    fn enabled(&self, _metadata: &Metadata) -> bool {
-        true
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filters.enabled(metadata.target(), metadata.level())
     }
 
     fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
         let level = match record.level() {
             log::Level::Error => DataDogLogLevel::Error,
             log::Level::Warn => DataDogLogLevel::Warning,
@@ -256,10 +482,23 @@ impl Log for DataDogLogger {
             log::Level::Debug | log::Level::Trace => DataDogLogLevel::Debug,
         };
 
-        &self.log(format!("{}", record.args()), level);
+        self.log_record(
+            record.args(),
+            level,
+            Some(record.target()),
+            Map::new(),
+            None,
+        );
     }
 
-    fn flush(&self) {}
+    /// Waits until all logs queued so far are sent, but no longer than `flush_timeout_ms` from config.
+    ///
+    /// Nonblocking logger can only be flushed if its future is polled on a different thread than the one calling `flush`.
+    fn flush(&self) {
+        if let Some(ref sender) = self.logsender {
+            LoggerMessage::flush(sender, Instant::now() + self.config.flush_timeout());
+        }
+    }
 }
 
 impl Drop for DataDogLogger {
@@ -0,0 +1,27 @@
+use super::log::DataDogLog;
+use flume::{bounded, Sender};
+use std::time::Instant;
+
+/// Message passed from logger to logger thread or task
+#[derive(Debug)]
+// nearly all messages are logs, boxing them would only add an allocation per log
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum LoggerMessage {
+    /// Log to be sent to DataDog
+    Log(DataDogLog),
+    /// Request to send all stored logs, acknowledged once they are sent
+    Flush(Sender<()>),
+}
+
+impl LoggerMessage {
+    /// Requests logger thread or task to send stored logs and waits for it until deadline
+    pub(crate) fn flush(sender: &Sender<LoggerMessage>, deadline: Instant) {
+        let (ack_sender, ack_receiver) = bounded(1);
+        if sender
+            .send_deadline(LoggerMessage::Flush(ack_sender), deadline)
+            .is_ok()
+        {
+            ack_receiver.recv_deadline(deadline).unwrap_or_default();
+        }
+    }
+}
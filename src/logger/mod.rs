@@ -1,10 +1,35 @@
+#[cfg(feature = "log4rs")]
+mod appender;
 mod blocking;
+mod builder;
+mod context;
+mod deduplication;
+#[cfg(feature = "slog")]
+mod drain;
+pub(crate) mod filter;
 mod level;
 mod log;
+#[allow(clippy::module_inception)]
 mod logger;
-#[cfg(feature = "nonblocking")]
+mod message;
+#[cfg(feature = "nonblocking-core")]
 mod nonblocking;
+mod panic_hook;
+mod pipeline;
+mod redaction;
+mod sampling;
+mod tags;
+mod tee;
+pub(crate) mod timestamp;
 
 pub use self::log::DataDogLog;
+#[cfg(feature = "log4rs")]
+pub use appender::{DataDogAppender, DataDogAppenderDeserializer};
+pub use builder::{DataDogLoggerBuilder, DataDogLoggerGuard};
+pub use context::{current_context, with_context, WithContext, WithContextExt};
+#[cfg(feature = "slog")]
+pub use drain::DataDogDrain;
 pub use level::DataDogLogLevel;
 pub use logger::DataDogLogger;
+pub use tags::Tags;
+pub use tee::{LocalFormat, LocalSink};
@@ -1,68 +1,128 @@
-use super::log::DataDogLog;
+use super::{log::DataDogLog, message::LoggerMessage, pipeline::Pipeline, redaction::Redactor};
 use crate::client::AsyncDataDogClient;
-use flume::{Receiver, RecvError, Sender, TryRecvError};
+use crate::config::DataDogConfig;
+use crate::processor::LogProcessor;
+use flume::{Receiver, Sender, TryRecvError};
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use std::{sync::Arc, time::Instant};
 
 pub(crate) async fn logger_future<T>(
     mut client: T,
-    logs: Receiver<DataDogLog>,
+    logs: Receiver<LoggerMessage>,
     mut selflog: Option<Sender<String>>,
+    config: DataDogConfig,
+    processors: Vec<Box<dyn LogProcessor>>,
+    redactor: Option<Arc<Redactor>>,
 ) where
     T: AsyncDataDogClient,
 {
     let mut store = Vec::new();
+    let mut pipeline = Pipeline::new(&config, processors, redactor);
     loop {
-        match logs.try_recv() {
-            Ok(msg) => {
-                if store.len() < 50 {
-                    store.push(msg);
-                    continue;
-                } else {
-                    store.push(msg);
-                    send(&mut client, &mut store, &mut selflog).await;
-                }
-            }
+        let message = match logs.try_recv() {
+            Ok(message) => Some(message),
             Err(TryRecvError::Empty) => {
-                if !store.is_empty() {
-                    send(&mut client, &mut store, &mut selflog).await;
-                }
-                // a trick not to spin endlessly on empty receiver
-                match logs.recv_async().await {
-                    Ok(msg) => {
-                        if store.len() < 50 {
-                            store.push(msg);
-                            continue;
-                        } else {
-                            store.push(msg);
-                            send(&mut client, &mut store, &mut selflog).await;
+                pipeline.expire(&mut store);
+                send(&mut client, &mut store, &mut selflog, &config).await;
+                // a trick not to spin endlessly on empty receiver, but only until pipeline has logs to release
+                let received = match pipeline.next_deadline() {
+                    Some(deadline) => {
+                        let delay = Delay::new(deadline.saturating_duration_since(Instant::now()));
+                        match future::select(logs.recv_async(), delay).await {
+                            Either::Left((received, _)) => Some(received),
+                            Either::Right(_) => None,
                         }
                     }
-                    Err(RecvError::Disconnected) => {
-                        if !store.is_empty() {
-                            send(&mut client, &mut store, &mut selflog).await;
-                        }
-                        break ();
-                    }
+                    None => Some(logs.recv_async().await),
+                };
+                match received {
+                    Some(Ok(message)) => Some(message),
+                    Some(Err(_)) => break,
+                    None => None,
                 }
             }
-            Err(TryRecvError::Disconnected) => {
-                if !store.is_empty() {
-                    send(&mut client, &mut store, &mut selflog).await;
+            Err(TryRecvError::Disconnected) => break,
+        };
+
+        match message {
+            Some(LoggerMessage::Log(msg)) => {
+                pipeline.process(msg, &mut store);
+                if store.len() >= config.batch_size {
+                    send(&mut client, &mut store, &mut selflog, &config).await;
                 }
-                break ();
             }
-        };
+            Some(LoggerMessage::Flush(ack)) => {
+                pipeline.drain(&mut store);
+                send(&mut client, &mut store, &mut selflog, &config).await;
+                flush(&mut client, &mut selflog).await;
+                ack.send_async(()).await.unwrap_or_default();
+            }
+            None => {
+                // pipeline deadline passed, logs are released in the next iteration
+            }
+        }
     }
+
+    pipeline.drain(&mut store);
+    send(&mut client, &mut store, &mut selflog, &config).await;
+    flush(&mut client, &mut selflog).await;
 }
 
-async fn send<T>(client: &mut T, logs: &mut Vec<DataDogLog>, selflog: &mut Option<Sender<String>>)
+/// Waits until client delivers logs it was given and reports its notices
+async fn flush<T>(client: &mut T, selflog: &mut Option<Sender<String>>)
 where
     T: AsyncDataDogClient,
 {
-    if let Err(e) = client.send_async(&logs).await {
+    client.flush_async().await;
+    for message in client.take_self_log() {
         if let Some(selflog) = selflog {
-            selflog.send_async(e.to_string()).await.unwrap_or_default()
+            selflog.try_send(message).unwrap_or_default();
         }
-    } else {
-        logs.clear();
     }
 }
+
+async fn send<T>(
+    client: &mut T,
+    logs: &mut Vec<DataDogLog>,
+    selflog: &mut Option<Sender<String>>,
+    config: &DataDogConfig,
+) where
+    T: AsyncDataDogClient,
+{
+    for batch in logs.chunks(config.batch_size.max(1)) {
+        let mut attempt = 0;
+        loop {
+            let result = client.send_async(batch).await;
+            // notices are taken even without self log, so that client does not accumulate them
+            for message in client.take_self_log() {
+                if let Some(selflog) = selflog {
+                    selflog.try_send(message).unwrap_or_default();
+                }
+            }
+            let e = match result {
+                Ok(()) => break,
+                Err(e) => e,
+            };
+            if let Some(selflog) = selflog {
+                selflog.try_send(e.to_string()).unwrap_or_default();
+            }
+            if attempt >= config.max_retries {
+                if let Some(selflog) = selflog {
+                    selflog
+                        .try_send(format!(
+                            "Dropping {} logs after {} failed attempts",
+                            batch.len(),
+                            attempt + 1
+                        ))
+                        .unwrap_or_default();
+                }
+                break;
+            }
+            Delay::new(config.retry_backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    logs.clear();
+}
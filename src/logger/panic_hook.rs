@@ -0,0 +1,63 @@
+use super::{
+    level::DataDogLogLevel,
+    log::{DataDogLog, StaticFields},
+    message::LoggerMessage,
+};
+use crate::config::DataDogConfig;
+use flume::WeakSender;
+use serde_json::{json, Map};
+use std::{backtrace::Backtrace, panic, thread, time::Instant};
+
+/// Installs panic hook logging panics with given config and sender, then calling previously set hook
+pub(crate) fn install(config: DataDogConfig, sender: WeakSender<LoggerMessage>) {
+    let fields = StaticFields::new(&config);
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // logger might already be dropped, in which case there is nothing to log to
+        if let Some(sender) = sender.upgrade() {
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("Box<dyn Any>");
+            let thread = thread::current();
+            let thread_name = thread.name().unwrap_or("<unnamed>");
+            let location = info
+                .location()
+                .map(|location| location.to_string())
+                .unwrap_or_default();
+
+            let mut attributes = Map::new();
+            attributes.insert(
+                "error".into(),
+                json!({
+                    "kind": "panic",
+                    "message": message,
+                    "stack": Backtrace::force_capture().to_string(),
+                }),
+            );
+            attributes.insert("logger".into(), json!({ "thread_name": thread_name }));
+
+            let log = DataDogLog::new(
+                &fields,
+                format!(
+                    "thread '{}' panicked at {}: {}",
+                    thread_name, location, message
+                ),
+                DataDogLogLevel::Critical,
+                attributes,
+            );
+
+            let deadline = Instant::now() + config.flush_timeout();
+            if sender
+                .send_deadline(LoggerMessage::Log(log), deadline)
+                .is_ok()
+            {
+                LoggerMessage::flush(&sender, deadline);
+            }
+        }
+
+        previous(info);
+    }));
+}
@@ -0,0 +1,71 @@
+use super::{deduplication::Deduplicator, log::DataDogLog, redaction::Redactor};
+use crate::{config::DataDogConfig, processor::LogProcessor};
+use std::{sync::Arc, time::Instant};
+
+/// Stages applied to logs by logger thread or task before they are sent
+pub(crate) struct Pipeline {
+    processors: Vec<Box<dyn LogProcessor>>,
+    redactor: Option<Arc<Redactor>>,
+    deduplicator: Option<Deduplicator>,
+}
+
+impl Pipeline {
+    /// Creates pipeline from config, registered processors and redactor shared with local sink
+    pub(crate) fn new(
+        config: &DataDogConfig,
+        processors: Vec<Box<dyn LogProcessor>>,
+        redactor: Option<Arc<Redactor>>,
+    ) -> Self {
+        Pipeline {
+            processors,
+            redactor,
+            deduplicator: config.deduplication.as_ref().map(Deduplicator::new),
+        }
+    }
+
+    /// Processes log and appends logs ready to be sent to output
+    pub(crate) fn process(&mut self, mut log: DataDogLog, output: &mut Vec<DataDogLog>) {
+        // processors go first, so that anything they add is redacted as well
+        for processor in self.processors.iter_mut() {
+            log = match processor.process(log) {
+                Some(log) => log,
+                None => return,
+            };
+        }
+        if let Some(ref redactor) = self.redactor {
+            // API key is resolved once per batch, when its first log is processed
+            if output.is_empty() {
+                redactor.refresh_api_keys();
+            }
+            redactor.redact(&mut log);
+        }
+        match self.deduplicator {
+            Some(ref mut deduplicator) => {
+                deduplicator.process(log);
+                deduplicator.expire(Instant::now(), output);
+            }
+            None => output.push(log),
+        }
+    }
+
+    /// Appends logs held by pipeline that became ready to be sent to output
+    pub(crate) fn expire(&mut self, output: &mut Vec<DataDogLog>) {
+        if let Some(ref mut deduplicator) = self.deduplicator {
+            deduplicator.expire(Instant::now(), output);
+        }
+    }
+
+    /// Appends all logs held by pipeline to output
+    pub(crate) fn drain(&mut self, output: &mut Vec<DataDogLog>) {
+        if let Some(ref mut deduplicator) = self.deduplicator {
+            deduplicator.drain(output);
+        }
+    }
+
+    /// Time when pipeline will have logs ready to be sent, if it holds any
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.deduplicator
+            .as_ref()
+            .and_then(Deduplicator::next_deadline)
+    }
+}
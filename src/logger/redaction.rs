@@ -0,0 +1,220 @@
+use super::log::DataDogLog;
+use crate::{
+    config::{ApiKeySource, DataDogConfig},
+    error::DataDogLoggerError,
+};
+use flume::Sender;
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::{
+    borrow::Cow,
+    sync::{Arc, RwLock, RwLockReadGuard},
+};
+
+const EMAIL_PATTERN: &str = r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b";
+const CARD_PATTERN: &str = r"\b\d(?:[ -]?\d){12,18}\b";
+const BEARER_PATTERN: &str = r"(?i)\b(bearer)\s+[a-z0-9\-._~+/]+=*";
+
+/// Replaces sensitive data in logs according to redaction rules
+#[derive(Debug)]
+pub(crate) struct Redactor {
+    rules: Vec<Rule>,
+    api_keys: Option<ApiKeys>,
+}
+
+/// API keys resolved from source so far, so that keys are redacted also after rotation.
+///
+/// Source is only resolved when keys are refreshed, once per batch, not for every log.
+#[derive(Debug)]
+struct ApiKeys {
+    source: ApiKeySource,
+    known: RwLock<Vec<String>>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    regex: Regex,
+    replacement: String,
+    // matches are only replaced if they pass Luhn checksum, to spare ordinary numbers
+    luhn: bool,
+}
+
+impl Redactor {
+    /// Compiles redaction rules from config.
+    ///
+    /// Invalid rules are skipped and returned as errors. Returns `None` if there is nothing to redact.
+    pub(crate) fn compile(config: &DataDogConfig) -> (Option<Self>, Vec<DataDogLoggerError>) {
+        let mut rules = Vec::new();
+        let mut errors = Vec::new();
+        let mut api_keys = None;
+
+        if config.redaction.builtin_rules {
+            rules.push(Rule::builtin(EMAIL_PATTERN, "[REDACTED_EMAIL]", false));
+            rules.push(Rule::builtin(CARD_PATTERN, "[REDACTED_CARD]", true));
+            rules.push(Rule::builtin(BEARER_PATTERN, "$1 [REDACTED]", false));
+            let keys = ApiKeys {
+                source: config.apikey.clone(),
+                known: RwLock::new(Vec::new()),
+            };
+            keys.refresh();
+            api_keys = Some(keys);
+        }
+
+        for rule in &config.redaction.rules {
+            match Regex::new(&rule.pattern) {
+                Ok(regex) => rules.push(Rule {
+                    regex,
+                    replacement: rule.replacement.clone(),
+                    luhn: false,
+                }),
+                Err(e) => errors.push(DataDogLoggerError::ConfigError(format!(
+                    "Invalid redaction pattern '{}' : {}",
+                    rule.pattern, e
+                ))),
+            }
+        }
+
+        let redactor = if rules.is_empty() {
+            None
+        } else {
+            Some(Redactor { rules, api_keys })
+        };
+        (redactor, errors)
+    }
+
+    /// Compiles redaction rules shared by logger thread or task and local sink.
+    /// Invalid rules are reported to self log.
+    pub(crate) fn shared(
+        config: &DataDogConfig,
+        selflog: &Option<Sender<String>>,
+    ) -> Option<Arc<Self>> {
+        let (redactor, errors) = Redactor::compile(config);
+        if let Some(selflog) = selflog {
+            for e in errors {
+                selflog.try_send(e.to_string()).unwrap_or_default();
+            }
+        }
+        redactor.map(Arc::new)
+    }
+
+    /// Checks that all redaction rules in config are valid
+    pub(crate) fn validate(config: &DataDogConfig) -> Result<(), DataDogLoggerError> {
+        match Redactor::compile(config).1.into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Resolves API key, so that it is redacted also after it rotates
+    pub(crate) fn refresh_api_keys(&self) {
+        if let Some(ref api_keys) = self.api_keys {
+            api_keys.refresh();
+        }
+    }
+
+    /// Redacts message and all string values of attributes
+    pub(crate) fn redact(&self, log: &mut DataDogLog) {
+        let api_keys = self.api_keys.as_ref().map(ApiKeys::known);
+        let api_keys = api_keys.as_deref().map(Vec::as_slice).unwrap_or_default();
+
+        if let Some(message) = self.redact_str(&log.message, api_keys) {
+            log.message = message;
+        }
+        for value in log.attributes.values_mut() {
+            self.redact_value(value, api_keys);
+        }
+    }
+
+    fn redact_value(&self, value: &mut Value, api_keys: &[String]) {
+        match value {
+            Value::String(text) => {
+                if let Some(redacted) = self.redact_str(text, api_keys) {
+                    *text = redacted;
+                }
+            }
+            Value::Array(values) => values
+                .iter_mut()
+                .for_each(|v| self.redact_value(v, api_keys)),
+            Value::Object(values) => values
+                .values_mut()
+                .for_each(|v| self.redact_value(v, api_keys)),
+            _ => {}
+        }
+    }
+
+    fn redact_str(&self, text: &str, api_keys: &[String]) -> Option<String> {
+        let mut redacted: Option<String> = None;
+        for key in api_keys {
+            let current = redacted.as_deref().unwrap_or(text);
+            if current.contains(key.as_str()) {
+                redacted = Some(current.replace(key.as_str(), "[REDACTED_API_KEY]"));
+            }
+        }
+        for rule in &self.rules {
+            let current = redacted.as_deref().unwrap_or(text);
+            if let Cow::Owned(replaced) = rule.replace(current) {
+                redacted = Some(replaced);
+            }
+        }
+        redacted
+    }
+}
+
+impl ApiKeys {
+    /// Adds current key to known ones if it rotated
+    fn refresh(&self) {
+        if let Ok(key) = self.source.get() {
+            if !key.is_empty() && !self.known().contains(&key) {
+                self.known
+                    .write()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .push(key);
+            }
+        }
+    }
+
+    fn known(&self) -> RwLockReadGuard<'_, Vec<String>> {
+        self.known
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Rule {
+    fn builtin(pattern: &str, replacement: &str, luhn: bool) -> Self {
+        Rule {
+            regex: Regex::new(pattern).expect("built-in redaction pattern is valid"),
+            replacement: replacement.into(),
+            luhn,
+        }
+    }
+
+    fn replace<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.luhn {
+            self.regex.replace_all(text, |captures: &Captures| {
+                if passes_luhn(&captures[0]) {
+                    self.replacement.clone()
+                } else {
+                    captures[0].to_string()
+                }
+            })
+        } else {
+            self.regex.replace_all(text, self.replacement.as_str())
+        }
+    }
+}
+
+fn passes_luhn(number: &str) -> bool {
+    let sum: u32 = number
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| match (i % 2, digit * 2) {
+            (1, doubled) if doubled > 9 => doubled - 9,
+            (1, doubled) => doubled,
+            _ => digit,
+        })
+        .sum();
+    sum % 10 == 0
+}
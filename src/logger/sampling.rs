@@ -0,0 +1,107 @@
+use super::{filter::matches_target, level::DataDogLogLevel};
+use crate::{
+    config::{DataDogConfig, DataDogSamplingRule},
+    error::DataDogLoggerError,
+};
+use serde_json::{Map, Value};
+use std::{
+    cell::Cell,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+/// Decision made by [`Sampler`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Sampling {
+    /// Log is kept. Contains sample rate if log was subject to sampling.
+    Keep(Option<f64>),
+    /// Log is dropped
+    Drop,
+}
+
+/// Decides which logs are kept according to sampling rules
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Sampler {
+    rules: Vec<DataDogSamplingRule>,
+}
+
+impl Sampler {
+    pub(crate) fn new(config: &DataDogConfig) -> Self {
+        Sampler {
+            rules: config.sampling.clone(),
+        }
+    }
+
+    /// Checks that sample rates of all rules are between 0 and 1
+    pub(crate) fn validate(config: &DataDogConfig) -> Result<(), DataDogLoggerError> {
+        match config
+            .sampling
+            .iter()
+            .find(|rule| !(0.0..=1.0).contains(&rule.sample_rate))
+        {
+            Some(rule) => Err(DataDogLoggerError::ConfigError(format!(
+                "Sample rate must be between 0 and 1, got {}",
+                rule.sample_rate
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn sample(
+        &self,
+        level: DataDogLogLevel,
+        target: Option<&str>,
+        attributes: &Map<String, Value>,
+    ) -> Sampling {
+        let rule = self.rules.iter().find(|rule| {
+            rule.level.map_or(true, |rule_level| level >= rule_level)
+                && rule.target.as_deref().map_or(true, |rule_target| {
+                    target.is_some_and(|target| matches_target(rule_target, target))
+                })
+        });
+
+        let rule = match rule {
+            Some(rule) => rule,
+            None => return Sampling::Keep(None),
+        };
+
+        let draw = match rule.key.as_deref().and_then(|key| attributes.get(key)) {
+            Some(Value::String(value)) => fnv1a(value.as_bytes()),
+            Some(value) => fnv1a(value.to_string().as_bytes()),
+            None => random(),
+        };
+
+        // draw is uniform over u64, so it falls below the threshold with probability of sample rate
+        if (draw as f64) < rule.sample_rate * u64::MAX as f64 || rule.sample_rate >= 1.0 {
+            Sampling::Keep(Some(rule.sample_rate))
+        } else {
+            Sampling::Drop
+        }
+    }
+}
+
+/// FNV-1a hash, stable across processes so that services sample the same keys
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    // final avalanche spreads similar keys, e.g. sequential ids, over the whole range
+    let hash = (hash ^ (hash >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^ (hash >> 33)
+}
+
+thread_local! {
+    static RANDOM_STATE: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+}
+
+/// Xorshift generator, good enough for sampling without additional dependencies
+fn random() -> u64 {
+    RANDOM_STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+        x
+    })
+}
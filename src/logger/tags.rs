@@ -0,0 +1,241 @@
+use crate::error::DataDogLoggerError;
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{fmt, str::FromStr, sync::Arc};
+
+const MAX_TAG_LENGTH: usize = 200;
+
+/// Ordered set of DataDog tags, e.g. `env:prod` or `critical`
+///
+/// Tags are normalized the way DataDog does it: they are lowercased, characters other than
+/// alphanumerics, underscores, minuses, colons, periods and slashes are replaced with underscores
+/// and tags are truncated to 200 characters. Tags not starting with a letter are rejected.
+/// Duplicated tags are kept only once, in place of their first occurence.
+///
+/// Cloning tags is cheap, as they are shared until modified.
+///
+/// Tags are serialized as a comma separated string. They can be deserialized from such string,
+/// a list of tags or a map of keys and values.
+///
+/// # Examples
+///```rust
+///use datadog_logs::logger::Tags;
+///
+///let mut tags: Tags = "env:prod,team:Payments".parse().unwrap();
+///tags.insert("region", "eu-west-1").unwrap();
+///tags.push("env:prod").unwrap();
+///
+///assert_eq!("env:prod,team:payments,region:eu-west-1", tags.to_string());
+///assert!(tags.push("1st").is_err());
+///```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tags {
+    tags: Arc<Vec<String>>,
+}
+
+impl Tags {
+    /// Creates empty tags
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates tags from a list of tags, e.g. `["env:prod", "critical"]`
+    pub fn from_list<I, S>(tags: I) -> Result<Self, DataDogLoggerError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut result = Tags::new();
+        for tag in tags {
+            result.push(tag)?;
+        }
+        Ok(result)
+    }
+
+    /// Creates `key:value` tags from pairs of keys and values, e.g. a map
+    pub fn from_pairs<I, K, V>(tags: I) -> Result<Self, DataDogLoggerError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut result = Tags::new();
+        for (key, value) in tags {
+            result.insert(key, value)?;
+        }
+        Ok(result)
+    }
+
+    /// Adds `key:value` tag
+    pub fn insert<K: AsRef<str>, V: AsRef<str>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<(), DataDogLoggerError> {
+        self.push(format!("{}:{}", key.as_ref(), value.as_ref()))
+    }
+
+    /// Sets `key:value` tag in place of tags with the same key, or adds it if there are none
+    pub fn replace<K: AsRef<str>, V: AsRef<str>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<(), DataDogLoggerError> {
+        let tag = normalize(&format!("{}:{}", key.as_ref(), value.as_ref()))?;
+        let key = tag
+            .split_once(':')
+            .map_or(tag.as_str(), |(key, _)| key)
+            .to_string();
+        let has_key = |tag: &String| matches!(tag.split_once(':'), Some((k, _)) if k == key);
+
+        let tags = Arc::make_mut(&mut self.tags);
+        match tags.iter().position(has_key) {
+            Some(position) => {
+                tags.retain(|tag| !has_key(tag));
+                tags.insert(position, tag);
+            }
+            None => tags.push(tag),
+        }
+        Ok(())
+    }
+
+    /// Adds tag, either `key:value` or a single value
+    pub fn push<S: AsRef<str>>(&mut self, tag: S) -> Result<(), DataDogLoggerError> {
+        let tag = normalize(tag.as_ref())?;
+        self.push_normalized(tag);
+        Ok(())
+    }
+
+    /// Adds all tags of other tags
+    pub fn extend(&mut self, other: &Tags) {
+        if self.tags.is_empty() {
+            self.tags = other.tags.clone();
+            return;
+        }
+        for tag in other.tags.iter() {
+            self.push_normalized(tag.clone());
+        }
+    }
+
+    /// Returns value of first tag with given key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tags.iter().find_map(|tag| match tag.split_once(':') {
+            Some((k, value)) if k == key => Some(value),
+            _ => None,
+        })
+    }
+
+    /// Iterates over tags in order they were added
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(String::as_str)
+    }
+
+    /// Returns number of tags
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Returns `true` if there are no tags
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    fn push_normalized(&mut self, tag: String) {
+        if !self.tags.contains(&tag) {
+            Arc::make_mut(&mut self.tags).push(tag);
+        }
+    }
+}
+
+fn normalize(tag: &str) -> Result<String, DataDogLoggerError> {
+    let mut normalized = String::with_capacity(tag.len());
+    let mut length = 0;
+    for c in tag.trim().chars().flat_map(char::to_lowercase) {
+        if length == MAX_TAG_LENGTH {
+            break;
+        }
+        let c = match c {
+            c if c.is_alphanumeric() => c,
+            '_' | '-' | ':' | '.' | '/' => c,
+            _ => '_',
+        };
+        // consecutive underscores are collapsed into one
+        if c == '_' && normalized.ends_with('_') {
+            continue;
+        }
+        normalized.push(c);
+        length += 1;
+    }
+    let normalized = normalized.trim_end_matches('_');
+
+    match normalized.chars().next() {
+        Some(c) if c.is_alphabetic() => Ok(normalized.to_string()),
+        _ => Err(DataDogLoggerError::ConfigError(format!(
+            "Tag `{}` does not start with a letter",
+            tag
+        ))),
+    }
+}
+
+impl fmt::Display for Tags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.tags.join(","))
+    }
+}
+
+impl FromStr for Tags {
+    type Err = DataDogLoggerError;
+
+    /// Parses comma separated tags
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tags::from_list(s.split(',').filter(|tag| !tag.trim().is_empty()))
+    }
+}
+
+impl Serialize for Tags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(TagsVisitor)
+    }
+}
+
+struct TagsVisitor;
+
+impl<'de> Visitor<'de> for TagsVisitor {
+    type Value = Tags;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("comma separated tags, a list of tags or a map of keys and values")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Tags::new())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut tags = Tags::new();
+        while let Some(tag) = seq.next_element::<String>()? {
+            tags.push(tag).map_err(de::Error::custom)?;
+        }
+        Ok(tags)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut tags = Tags::new();
+        while let Some((key, value)) = map.next_entry::<String, String>()? {
+            tags.insert(key, value).map_err(de::Error::custom)?;
+        }
+        Ok(tags)
+    }
+}
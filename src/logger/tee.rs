@@ -0,0 +1,110 @@
+use super::{log::DataDogLog, redaction::Redactor, timestamp::rfc3339};
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// Format of logs written to [`LocalSink`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalFormat {
+    /// Human readable line with timestamp, level, message and attributes
+    Text,
+    /// DataDog log serialized as a single JSON line
+    Json,
+}
+
+/// Secondary destination of logs, written synchronously on the calling thread.
+///
+/// It is independent of DataDog channel, so logs are written locally even when DataDog is unreachable or channel is full.
+/// Logs are redacted with redaction rules of the logger before they are written, as local output,
+/// e.g. standard error of a container, is often collected by DataDog agent too.
+/// Processors of the logger are not applied to them.
+pub struct LocalSink {
+    writer: Mutex<Box<dyn Write + Send>>,
+    format: LocalFormat,
+    redactor: Option<Arc<Redactor>>,
+}
+
+impl fmt::Debug for LocalSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSink")
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+impl LocalSink {
+    /// Writes logs to standard error
+    pub fn stderr(format: LocalFormat) -> Self {
+        Self::writer(io::stderr(), format)
+    }
+
+    /// Writes logs to standard output
+    pub fn stdout(format: LocalFormat) -> Self {
+        Self::writer(io::stdout(), format)
+    }
+
+    /// Appends logs to a file, creating it if it does not exist
+    pub fn file<P: AsRef<Path>>(path: P, format: LocalFormat) -> io::Result<Self> {
+        let file: File = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::writer(file, format))
+    }
+
+    /// Writes logs to any writer
+    pub fn writer<W: Write + Send + 'static>(writer: W, format: LocalFormat) -> Self {
+        LocalSink {
+            writer: Mutex::new(Box::new(writer)),
+            format,
+            redactor: None,
+        }
+    }
+
+    pub(crate) fn with_redactor(mut self, redactor: Option<Arc<Redactor>>) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    pub(crate) fn write(&self, log: &DataDogLog) -> io::Result<()> {
+        let redacted;
+        let log = match self.redactor {
+            Some(ref redactor) => {
+                let mut log = log.clone();
+                redactor.redact(&mut log);
+                redacted = log;
+                &redacted
+            }
+            None => log,
+        };
+
+        let line = match self.format {
+            LocalFormat::Json => serde_json::to_string(log)?,
+            LocalFormat::Text => format_text(log),
+        };
+
+        // a panic while writing does not leave the writer in a state worth giving up on
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        writeln!(writer, "{}", line)?;
+        writer.flush()
+    }
+}
+
+fn format_text(log: &DataDogLog) -> String {
+    let mut line = format!(
+        "{} {:<7} {}",
+        rfc3339(SystemTime::now()),
+        log.level.to_uppercase(),
+        log.message
+    );
+    if !log.attributes.is_empty() {
+        line.push(' ');
+        line.push_str(&serde_json::Value::from(log.attributes.clone()).to_string());
+    }
+    line
+}
@@ -0,0 +1,41 @@
+use super::LogProcessor;
+use crate::logger::DataDogLog;
+use serde_json::{Map, Value};
+
+/// Adds the same attributes to every log.
+///
+/// Attributes already present in a log are not overwritten.
+#[derive(Debug, Clone, Default)]
+pub struct StaticAttributesProcessor {
+    attributes: Map<String, Value>,
+}
+
+impl StaticAttributesProcessor {
+    /// Creates processor without attributes
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds attribute
+    pub fn attribute<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl From<Map<String, Value>> for StaticAttributesProcessor {
+    fn from(attributes: Map<String, Value>) -> Self {
+        StaticAttributesProcessor { attributes }
+    }
+}
+
+impl LogProcessor for StaticAttributesProcessor {
+    fn process(&mut self, mut log: DataDogLog) -> Option<DataDogLog> {
+        for (key, value) in &self.attributes {
+            log.attributes
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        Some(log)
+    }
+}
@@ -0,0 +1,119 @@
+use super::LogProcessor;
+use crate::logger::{DataDogLog, Tags};
+use regex::Regex;
+use std::{env, fs, path::Path};
+
+/// Downward API environment variables and tags they are sent as
+const DOWNWARD_API_VARIABLES: [(&str, &str); 4] = [
+    ("POD_NAME", "pod_name"),
+    ("POD_NAMESPACE", "kube_namespace"),
+    ("NODE_NAME", "kube_node"),
+    ("CONTAINER_NAME", "kube_container_name"),
+];
+
+/// Tags logs with metadata of container and Kubernetes pod the process runs in
+///
+/// Without DataDog agent collecting logs, they lack tags identifying the workload.
+/// This processor detects them once and adds them to every log:
+/// * `container_id`, read from `/proc/self/cgroup` or, with cgroup v2, from `/proc/self/mountinfo`,
+/// * `pod_name`, `kube_namespace`, `kube_node` and `kube_container_name`, read from `POD_NAME`, `POD_NAMESPACE`,
+///   `NODE_NAME` and `CONTAINER_NAME` environment variables, which have to be exposed to the container with downward API.
+///
+/// Metadata that cannot be found is skipped.
+///
+/// # Examples
+///```rust
+///use datadog_logs::logger::DataDogLogger;
+///use datadog_logs::processor::ContainerMetadataProcessor;
+///
+///let logger = DataDogLogger::builder()
+///    .processor(ContainerMetadataProcessor::detect())
+///    .build_blocking()
+///    .unwrap();
+///```
+#[derive(Debug, Clone, Default)]
+pub struct ContainerMetadataProcessor {
+    tags: Tags,
+}
+
+impl ContainerMetadataProcessor {
+    /// Creates processor without any metadata
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates processor with metadata of the container the process runs in
+    pub fn detect() -> Self {
+        Self::new()
+            .with_files("/proc/self/cgroup", "/proc/self/mountinfo")
+            .with_downward_api()
+    }
+
+    /// Adds container id found in given cgroup and mountinfo files. Files that cannot be read are skipped.
+    pub fn with_files<P: AsRef<Path>, Q: AsRef<Path>>(mut self, cgroup: P, mountinfo: Q) -> Self {
+        let container_id = fs::read_to_string(cgroup)
+            .ok()
+            .and_then(|cgroup| container_id_from_cgroup(&cgroup))
+            .or_else(|| {
+                fs::read_to_string(mountinfo)
+                    .ok()
+                    .and_then(|mountinfo| container_id_from_mountinfo(&mountinfo))
+            });
+        if let Some(container_id) = container_id {
+            self.insert("container_id", &container_id);
+        }
+        self
+    }
+
+    /// Adds pod metadata exposed with downward API environment variables
+    pub fn with_downward_api(mut self) -> Self {
+        for (variable, tag) in DOWNWARD_API_VARIABLES {
+            if let Ok(value) = env::var(variable) {
+                if !value.is_empty() {
+                    self.insert(tag, &value);
+                }
+            }
+        }
+        self
+    }
+
+    /// Exposes detected metadata
+    pub fn tags(&self) -> &Tags {
+        &self.tags
+    }
+
+    fn insert(&mut self, key: &str, value: &str) {
+        // keys start with a letter, hence tags are always valid
+        self.tags.insert(key, value).unwrap_or_default();
+    }
+}
+
+/// Container id is the last 64 characters long hexadecimal segment of any cgroup path,
+/// e.g. `/docker/<id>` or `/kubepods/burstable/pod<uid>/cri-containerd-<id>.scope`
+fn container_id_from_cgroup(cgroup: &str) -> Option<String> {
+    let pattern = Regex::new(r"(?:^|[/\-])([0-9a-f]{64})(?:\.scope)?$").unwrap();
+    cgroup.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        pattern
+            .captures(path)
+            .map(|captures| captures[1].to_string())
+    })
+}
+
+/// With cgroup v2 cgroup path is not visible inside container, but container runtimes
+/// mount files from directory named after the container, e.g. `/var/lib/docker/containers/<id>/hostname`
+fn container_id_from_mountinfo(mountinfo: &str) -> Option<String> {
+    let pattern = Regex::new(r"/containers/([0-9a-f]{64})/").unwrap();
+    mountinfo.lines().find_map(|line| {
+        pattern
+            .captures(line)
+            .map(|captures| captures[1].to_string())
+    })
+}
+
+impl LogProcessor for ContainerMetadataProcessor {
+    fn process(&mut self, mut log: DataDogLog) -> Option<DataDogLog> {
+        log.ddtags.extend(&self.tags);
+        Some(log)
+    }
+}
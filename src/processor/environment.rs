@@ -0,0 +1,32 @@
+use super::LogProcessor;
+use crate::logger::{DataDogLog, Tags};
+use std::env;
+
+/// Adds `env:<environment>` tag to every log
+#[derive(Debug, Clone)]
+pub struct EnvironmentTagProcessor {
+    tags: Tags,
+}
+
+impl EnvironmentTagProcessor {
+    /// Creates processor tagging logs with given environment
+    pub fn new<S: AsRef<str>>(environment: S) -> Self {
+        let mut tags = Tags::new();
+        // tag starts with `env`, hence it is always valid
+        tags.insert("env", environment).unwrap_or_default();
+        EnvironmentTagProcessor { tags }
+    }
+
+    /// Creates processor tagging logs with environment read from environment variable, e.g. `DD_ENV`.
+    /// Returns `None` if variable is not set.
+    pub fn from_env_var(variable: &str) -> Option<Self> {
+        env::var(variable).ok().map(EnvironmentTagProcessor::new)
+    }
+}
+
+impl LogProcessor for EnvironmentTagProcessor {
+    fn process(&mut self, mut log: DataDogLog) -> Option<DataDogLog> {
+        log.ddtags.extend(&self.tags);
+        Some(log)
+    }
+}
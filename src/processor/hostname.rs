@@ -0,0 +1,35 @@
+use super::LogProcessor;
+use crate::logger::DataDogLog;
+use std::sync::Arc;
+
+/// Sets host of logs that do not have one
+#[derive(Debug, Clone)]
+pub struct HostnameProcessor {
+    hostname: Arc<str>,
+}
+
+impl HostnameProcessor {
+    /// Creates processor setting given hostname
+    pub fn new<S: AsRef<str>>(hostname: S) -> Self {
+        HostnameProcessor {
+            hostname: hostname.as_ref().into(),
+        }
+    }
+
+    /// Creates processor setting hostname of the machine.
+    ///
+    /// It is detected the same way as [`detect_hostname`](crate::config::DataDogConfig::detect_hostname) does it.
+    /// Returns `None` if hostname cannot be determined.
+    pub fn detect() -> Option<Self> {
+        crate::hostname::detect().map(HostnameProcessor::new)
+    }
+}
+
+impl LogProcessor for HostnameProcessor {
+    fn process(&mut self, mut log: DataDogLog) -> Option<DataDogLog> {
+        if log.host.is_empty() {
+            log.host = self.hostname.clone();
+        }
+        Some(log)
+    }
+}
@@ -0,0 +1,48 @@
+mod attributes;
+mod container;
+mod environment;
+mod hostname;
+mod rename;
+
+pub use attributes::StaticAttributesProcessor;
+pub use container::ContainerMetadataProcessor;
+pub use environment::EnvironmentTagProcessor;
+pub use hostname::HostnameProcessor;
+pub use rename::RenameFieldProcessor;
+
+use crate::logger::DataDogLog;
+
+/// Describes a stage that enriches, transforms or drops logs before they are sent to DataDog
+///
+/// Processors run on logger thread or task in order they were registered, so they do not slow down logging.
+/// They run before redaction and deduplication.
+///
+/// Any `FnMut(DataDogLog) -> Option<DataDogLog>` closure is a processor.
+///
+/// # Examples
+///```rust
+///use datadog_logs::logger::DataDogLogger;
+///use datadog_logs::processor::StaticAttributesProcessor;
+///
+///let logger = DataDogLogger::builder()
+///    .processor(StaticAttributesProcessor::new().attribute("team", "payments"))
+///    .processor(|log: datadog_logs::logger::DataDogLog| {
+///        // drop health checks
+///        if log.message.contains("/health") { None } else { Some(log) }
+///    })
+///    .build_blocking()
+///    .unwrap();
+///```
+pub trait LogProcessor: Send {
+    /// Processes log. Returning `None` drops it.
+    fn process(&mut self, log: DataDogLog) -> Option<DataDogLog>;
+}
+
+impl<F> LogProcessor for F
+where
+    F: FnMut(DataDogLog) -> Option<DataDogLog> + Send,
+{
+    fn process(&mut self, log: DataDogLog) -> Option<DataDogLog> {
+        self(log)
+    }
+}
@@ -0,0 +1,61 @@
+use super::LogProcessor;
+use crate::logger::DataDogLog;
+use serde_json::{Map, Value};
+
+/// Moves attribute to a different name, e.g. to match DataDog standard attributes.
+///
+/// Names are paths with segments separated by dots, so nested attributes can be renamed as well.
+/// If target attribute already exists, it is overwritten.
+#[derive(Debug, Clone)]
+pub struct RenameFieldProcessor {
+    from: String,
+    to: String,
+}
+
+impl RenameFieldProcessor {
+    /// Creates processor renaming attribute `from` to `to`
+    pub fn new<F: Into<String>, T: Into<String>>(from: F, to: T) -> Self {
+        RenameFieldProcessor {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+
+impl LogProcessor for RenameFieldProcessor {
+    fn process(&mut self, mut log: DataDogLog) -> Option<DataDogLog> {
+        if let Some(value) = remove_path(&mut log.attributes, &self.from) {
+            insert_path(&mut log.attributes, &self.to, value);
+        }
+        Some(log)
+    }
+}
+
+fn remove_path(attributes: &mut Map<String, Value>, path: &str) -> Option<Value> {
+    match path.split_once('.') {
+        Some((head, rest)) => match attributes.get_mut(head) {
+            Some(Value::Object(nested)) => remove_path(nested, rest),
+            _ => None,
+        },
+        None => attributes.remove(path),
+    }
+}
+
+fn insert_path(attributes: &mut Map<String, Value>, path: &str, value: Value) {
+    match path.split_once('.') {
+        Some((head, rest)) => {
+            let nested = attributes
+                .entry(head)
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !nested.is_object() {
+                *nested = Value::Object(Map::new());
+            }
+            if let Value::Object(nested) = nested {
+                insert_path(nested, rest, value);
+            }
+        }
+        None => {
+            attributes.insert(path.to_string(), value);
+        }
+    }
+}
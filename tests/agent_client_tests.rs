@@ -0,0 +1,186 @@
+use datadog_logs::{
+    client::{AgentDataDogClient, DataDogClient},
+    config::{DataDogAgentConfig, DataDogConfig},
+    logger::DataDogLog,
+};
+use std::io::{BufRead, BufReader};
+use std::net::{TcpListener, UdpSocket};
+
+fn client(url: String, max_datagram_size: usize) -> AgentDataDogClient {
+    AgentDataDogClient::new(&DataDogConfig {
+        apikey: "secret".into(),
+        agent_config: DataDogAgentConfig {
+            url,
+            max_datagram_size,
+        },
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+fn log(message: &str) -> DataDogLog {
+    DataDogLog {
+        message: message.into(),
+        ddtags: Default::default(),
+        ddsource: "rust".into(),
+        host: "host".into(),
+        service: "service".into(),
+        env: None,
+        version: None,
+        level: "info".into(),
+        attributes: Default::default(),
+    }
+}
+
+fn messages(lines: &str) -> Vec<String> {
+    lines
+        .lines()
+        .map(|line| serde_json::from_str::<DataDogLog>(line).unwrap().message)
+        .collect()
+}
+
+#[test]
+fn test_udp_client_splits_logs_into_datagrams() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let line_length = serde_json::to_vec(&log("a")).unwrap().len() + 1;
+    // two logs fit in a datagram, apart from the long one that is truncated to fit alone
+    let mut client = client(
+        format!("udp://{}", socket.local_addr().unwrap()),
+        2 * line_length,
+    );
+
+    let long = "a".repeat(3 * line_length);
+    client
+        .send(&[log("a"), log("b"), log("c"), log(&long), log("d")])
+        .unwrap();
+
+    let mut buffer = [0; 65536];
+    let mut datagrams = Vec::new();
+    for _ in 0..4 {
+        let size = socket.recv(&mut buffer).unwrap();
+        assert!(size <= 2 * line_length);
+        datagrams.push(String::from_utf8(buffer[..size].to_vec()).unwrap());
+    }
+
+    assert_eq!(vec!["a", "b"], messages(&datagrams[0]));
+    assert_eq!(vec!["c"], messages(&datagrams[1]));
+    let truncated = messages(&datagrams[2]).remove(0);
+    assert!(truncated.ends_with("..."));
+    assert!(long.starts_with(truncated.trim_end_matches("...")));
+    assert_eq!(vec!["d"], messages(&datagrams[3]));
+    assert!(!datagrams.concat().contains("secret"));
+
+    let notices = client.take_self_log();
+    assert_eq!(1, notices.len());
+    assert!(notices[0].starts_with("Truncating message of log"));
+}
+
+#[test]
+fn test_udp_client_drops_log_that_does_not_fit_datagram() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let line_length = serde_json::to_vec(&log("a")).unwrap().len() + 1;
+    let mut client = client(
+        format!("udp://{}", socket.local_addr().unwrap()),
+        line_length,
+    );
+
+    let mut large = log("a");
+    large
+        .attributes
+        .insert("payload".into(), "b".repeat(line_length).into());
+    client.send(&[large, log("c")]).unwrap();
+
+    let mut buffer = [0; 65536];
+    let size = socket.recv(&mut buffer).unwrap();
+    assert_eq!(
+        vec!["c"],
+        messages(std::str::from_utf8(&buffer[..size]).unwrap())
+    );
+    let notices = client.take_self_log();
+    assert_eq!(1, notices.len());
+    assert!(notices[0].starts_with("Dropping log of"));
+}
+
+#[test]
+fn test_tcp_client_sends_newline_delimited_json() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = client(format!("tcp://{}", listener.local_addr().unwrap()), 9000);
+
+    client.send(&[log("first"), log("second")]).unwrap();
+
+    let (stream, _) = listener.accept().unwrap();
+    let lines: Vec<String> = BufReader::new(stream)
+        .lines()
+        .take(2)
+        .map(|line| line.unwrap())
+        .collect();
+    assert_eq!(vec!["first", "second"], messages(&lines.join("\n")));
+    assert!(lines[0].starts_with('{'));
+}
+
+#[test]
+fn test_invalid_url_is_rejected() {
+    let config = |url: &str| DataDogConfig {
+        agent_config: DataDogAgentConfig {
+            url: url.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(AgentDataDogClient::new(&config("127.0.0.1:10518")).is_err());
+    assert!(AgentDataDogClient::new(&config("http://127.0.0.1:10518")).is_err());
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use std::os::unix::net::{UnixDatagram, UnixListener};
+    use std::path::PathBuf;
+
+    fn socket_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("datadog-logs-{}-{}.sock", std::process::id(), name));
+        std::fs::remove_file(&path).unwrap_or_default();
+        path
+    }
+
+    #[test]
+    fn test_unix_stream_client_sends_newline_delimited_json() {
+        let path = socket_path("stream");
+        let listener = UnixListener::bind(&path).unwrap();
+        let mut client = client(format!("unix://{}", path.display()), 9000);
+
+        client.send(&[log("first"), log("second")]).unwrap();
+
+        let (stream, _) = listener.accept().unwrap();
+        let lines: Vec<String> = BufReader::new(stream)
+            .lines()
+            .take(2)
+            .map(|line| line.unwrap())
+            .collect();
+        assert_eq!(vec!["first", "second"], messages(&lines.join("\n")));
+    }
+
+    #[test]
+    fn test_unix_datagram_client_splits_logs_into_datagrams() {
+        let path = socket_path("datagram");
+        let socket = UnixDatagram::bind(&path).unwrap();
+        let line_length = serde_json::to_vec(&log("a")).unwrap().len() + 1;
+        let mut client = client(format!("unixgram://{}", path.display()), line_length);
+
+        client.send(&[log("a"), log("b")]).unwrap();
+
+        let mut buffer = [0; 65536];
+        let size = socket.recv(&mut buffer).unwrap();
+        assert_eq!(
+            vec!["a"],
+            messages(std::str::from_utf8(&buffer[..size]).unwrap())
+        );
+        let size = socket.recv(&mut buffer).unwrap();
+        assert_eq!(
+            vec!["b"],
+            messages(std::str::from_utf8(&buffer[..size]).unwrap())
+        );
+    }
+}
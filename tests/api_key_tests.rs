@@ -0,0 +1,116 @@
+mod utils;
+
+use datadog_logs::{
+    client::HttpDataDogClient,
+    config::{ApiKeySource, DataDogConfig},
+    logger::{DataDogLogLevel, DataDogLogger},
+};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use utils::http::HttpStandIn;
+
+fn logger(stand_in: &HttpStandIn, apikey: ApiKeySource) -> DataDogLogger {
+    let config = DataDogConfig {
+        apikey,
+        ..Default::default()
+    };
+    let mut client_config = config.clone();
+    client_config.http_config.url = stand_in.url.clone();
+    DataDogLogger::blocking(HttpDataDogClient::new(&client_config).unwrap(), config)
+}
+
+fn sent_api_key(logger: &DataDogLogger, stand_in: &HttpStandIn) -> String {
+    logger.log("message", DataDogLogLevel::Informational);
+    log::Log::flush(logger);
+    let request = stand_in
+        .requests
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap();
+    request.header("DD-API-KEY").unwrap().to_string()
+}
+
+#[test]
+fn test_key_file_rotation_takes_effect_without_restart() {
+    let path = std::env::temp_dir().join(format!("datadog-logs-{}-apikey", std::process::id()));
+    fs::write(&path, "initial-key\n").unwrap();
+    let stand_in = HttpStandIn::start();
+    let logger = logger(&stand_in, ApiKeySource::file(&path));
+
+    assert_eq!("initial-key", sent_api_key(&logger, &stand_in));
+    assert_eq!("initial-key", sent_api_key(&logger, &stand_in));
+
+    fs::write(&path, "rotated-key-2\n").unwrap();
+    assert_eq!("rotated-key-2", sent_api_key(&logger, &stand_in));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_provider_rotation_takes_effect_without_restart() {
+    let current = Arc::new(Mutex::new(String::from("first-key")));
+    let provider_key = current.clone();
+    let stand_in = HttpStandIn::start();
+    let logger = logger(
+        &stand_in,
+        ApiKeySource::provider(move || Ok(provider_key.lock().unwrap().clone())),
+    );
+
+    assert_eq!("first-key", sent_api_key(&logger, &stand_in));
+    *current.lock().unwrap() = "second-key".into();
+    assert_eq!("second-key", sent_api_key(&logger, &stand_in));
+}
+
+#[test]
+fn test_key_is_not_revealed_by_debug_or_serialization() {
+    let config = DataDogConfig {
+        apikey: "very-secret-key".into(),
+        ..Default::default()
+    };
+
+    let debug = format!("{:?}", config);
+    let json = serde_json::to_string(&config).unwrap();
+
+    assert!(!debug.contains("very-secret-key"));
+    assert!(!json.contains("very-secret-key"));
+    assert!(json.contains(r#""apikey":"<redacted>""#));
+    assert_eq!("very-secret-key", config.apikey.get().unwrap());
+}
+
+#[test]
+fn test_key_sources_are_deserialized() {
+    let sources: Vec<ApiKeySource> = serde_yaml::from_str(
+        r#"
+- literal-key
+- env: DD_API_KEY
+- file: /run/secrets/datadog
+"#,
+    )
+    .unwrap();
+
+    assert_eq!("literal-key", sources[0].get().unwrap());
+    assert_eq!(
+        r#"ApiKeySource::Env("DD_API_KEY")"#,
+        format!("{:?}", sources[1])
+    );
+    assert_eq!(
+        r#"ApiKeySource::File("/run/secrets/datadog")"#,
+        format!("{:?}", sources[2])
+    );
+    assert_eq!(
+        r#"[{"env":"DD_API_KEY"},{"file":"/run/secrets/datadog"}]"#,
+        serde_json::to_string(&sources[1..]).unwrap()
+    );
+}
+
+#[test]
+fn test_redacted_key_is_not_deserialized() {
+    let config = DataDogConfig {
+        apikey: "very-secret-key".into(),
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&config).unwrap();
+
+    let error = serde_json::from_str::<DataDogConfig>(&json).unwrap_err();
+    assert!(error.to_string().contains("API key was redacted"));
+}
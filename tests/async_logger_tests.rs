@@ -1,3 +1,4 @@
+#![cfg(feature = "with-tokio")]
 mod utils;
 
 use datadog_logs::{
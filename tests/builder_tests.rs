@@ -0,0 +1,116 @@
+mod utils;
+
+use datadog_logs::logger::{DataDogLog, DataDogLogLevel, DataDogLogger};
+use flume::unbounded;
+use log::{info, Level, LevelFilter, Log, Record};
+use std::time::Duration;
+
+#[test]
+fn test_builder_uses_custom_client() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .service("builder-service")
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log("message", DataDogLogLevel::Error);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!(1, messages.len());
+    assert_eq!("builder-service", &*messages[0].service);
+}
+
+#[test]
+fn test_builder_filters_by_level_and_target() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .level(LevelFilter::Warn)
+        .filter("noisy", LevelFilter::Error)
+        .filter("chatty::module", LevelFilter::Debug)
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    let log = |target: &str, level: Level| {
+        Log::log(
+            &logger,
+            &Record::builder()
+                .args(format_args!("{}", target))
+                .target(target)
+                .level(level)
+                .build(),
+        )
+    };
+    log("app", Level::Warn);
+    log("app", Level::Info);
+    log("noisy::submodule", Level::Warn);
+    log("noisy", Level::Error);
+    log("noisyneighbour", Level::Warn);
+    log("chatty::module::inner", Level::Debug);
+    log("chatty", Level::Debug);
+
+    std::mem::drop(logger);
+
+    let messages: Vec<String> = receiver.iter().map(|log| log.message).collect();
+    assert_eq!(
+        vec!["app", "noisy", "noisyneighbour", "chatty::module::inner"],
+        messages
+    );
+}
+
+#[test]
+fn test_flush_sends_queued_logs() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .batch_size(1000)
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    for i in 0..10 {
+        logger.log(format!("message{}", i), DataDogLogLevel::Informational);
+    }
+    Log::flush(&logger);
+
+    assert_eq!(10, receiver.try_iter().count());
+}
+
+#[test]
+fn test_failed_batch_is_dropped_after_retries() {
+    let (sender, receiver) = unbounded();
+    let mut client = utils::DataDogClientStub::new(sender);
+    client.should_error = true;
+    let logger = DataDogLogger::builder()
+        .self_log(true)
+        .retry(2, Duration::from_millis(1))
+        .client(client)
+        .build_blocking()
+        .unwrap();
+
+    logger.log("message", DataDogLogLevel::Error);
+    Log::flush(&logger);
+
+    let selflog: Vec<String> = logger.selflog().as_ref().unwrap().try_iter().collect();
+    assert_eq!(4, selflog.len());
+    assert_eq!("Dropping 1 logs after 3 failed attempts", selflog[3]);
+    assert_eq!(0, receiver.try_iter().count());
+}
+
+#[test]
+fn test_install_blocking_flushes_on_guard_drop() {
+    let (sender, receiver) = unbounded();
+    let guard = DataDogLogger::builder()
+        .level(LevelFilter::Info)
+        .client(utils::DataDogClientStub::new(sender))
+        .install_blocking()
+        .unwrap();
+
+    info!("installed");
+    std::mem::drop(guard);
+
+    let messages: Vec<String> = receiver.try_iter().map(|log| log.message).collect();
+    assert_eq!(vec!["installed"], messages);
+    assert_eq!(LevelFilter::Info, log::max_level());
+}
@@ -0,0 +1,94 @@
+mod utils;
+
+use datadog_logs::{
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger},
+    processor::ContainerMetadataProcessor,
+};
+use flume::unbounded;
+use std::path::PathBuf;
+
+fn fixture(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", name]
+        .iter()
+        .collect()
+}
+
+#[test]
+fn test_container_id_is_read_from_cgroup() {
+    let docker = ContainerMetadataProcessor::new()
+        .with_files(fixture("cgroup_v1_docker"), fixture("mountinfo_host"));
+    let kubernetes = ContainerMetadataProcessor::new()
+        .with_files(fixture("cgroup_v1_kubernetes"), fixture("mountinfo_host"));
+
+    assert_eq!(
+        Some("3c5a1c64e9f1b2d3a4b5c6d7e8f90a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f"),
+        docker.tags().get("container_id")
+    );
+    assert_eq!(
+        Some("9f8e7d6c5b4a39281706f5e4d3c2b1a09f8e7d6c5b4a39281706f5e4d3c2b1a0"),
+        kubernetes.tags().get("container_id")
+    );
+}
+
+#[test]
+fn test_container_id_is_read_from_mountinfo_with_cgroup_v2() {
+    let processor = ContainerMetadataProcessor::new()
+        .with_files(fixture("cgroup_v2"), fixture("mountinfo_docker"));
+
+    assert_eq!(
+        Some("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"),
+        processor.tags().get("container_id")
+    );
+}
+
+#[test]
+fn test_missing_metadata_is_skipped() {
+    let host = ContainerMetadataProcessor::new()
+        .with_files(fixture("cgroup_v2"), fixture("mountinfo_host"));
+    let missing =
+        ContainerMetadataProcessor::new().with_files(fixture("missing"), fixture("missing"));
+
+    assert!(host.tags().is_empty());
+    assert!(missing.tags().is_empty());
+}
+
+#[test]
+fn test_metadata_is_added_to_every_log() {
+    let processor = utils::env::with_vars(
+        &[
+            ("POD_NAME", Some("checkout-7d9f8b-x2k4q")),
+            ("POD_NAMESPACE", Some("shop")),
+            ("NODE_NAME", Some("node-3")),
+            ("CONTAINER_NAME", Some("checkout")),
+        ],
+        || {
+            ContainerMetadataProcessor::new()
+                .with_files(fixture("cgroup_v2"), fixture("mountinfo_docker"))
+                .with_downward_api()
+        },
+    );
+
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .tags("team:core".parse().unwrap())
+        .processor(processor)
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log("first", DataDogLogLevel::Informational);
+    logger.log("second", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!(2, messages.len());
+    for message in messages {
+        assert_eq!(
+            "team:core,\
+            container_id:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef,\
+            pod_name:checkout-7d9f8b-x2k4q,kube_namespace:shop,kube_node:node-3,\
+            kube_container_name:checkout",
+            message.ddtags.to_string()
+        );
+    }
+}
@@ -0,0 +1,110 @@
+mod utils;
+
+use datadog_logs::logger::{
+    current_context, with_context, DataDogLog, DataDogLogLevel, DataDogLogger,
+};
+use flume::unbounded;
+use log::{Log, Record};
+use serde_json::json;
+
+#[test]
+fn test_context_fields_are_attached_inside_scope_only() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    with_context(
+        [("request_id", json!("42")), ("tenant", json!("acme"))],
+        || {
+            with_context([("tenant", "other"), ("user", "bob")], || {
+                Log::log(
+                    &logger,
+                    &Record::builder()
+                        .args(format_args!("inner"))
+                        .target("app")
+                        .build(),
+                );
+            });
+            logger.log("outer", DataDogLogLevel::Informational);
+        },
+    );
+    logger.log("outside", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!(3, messages.len());
+
+    assert_eq!("42", messages[0].attributes["request_id"]);
+    assert_eq!("other", messages[0].attributes["tenant"]);
+    assert_eq!("bob", messages[0].attributes["user"]);
+    assert_eq!(json!({"name": "app"}), messages[0].attributes["logger"]);
+
+    assert_eq!("acme", messages[1].attributes["tenant"]);
+    assert!(!messages[1].attributes.contains_key("user"));
+
+    assert!(messages[2].attributes.is_empty());
+}
+
+#[test]
+fn test_context_is_left_after_panic() {
+    let result = std::panic::catch_unwind(|| {
+        with_context([("request_id", "42")], || panic!("failure"));
+    });
+
+    assert!(result.is_err());
+    assert!(current_context().is_empty());
+}
+
+#[test]
+fn test_context_can_be_passed_to_another_thread() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    with_context([("request_id", "42")], || {
+        let context = current_context();
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                with_context(context, || {
+                    logger.log("worker", DataDogLogLevel::Informational)
+                })
+            });
+        });
+    });
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!("42", messages[0].attributes["request_id"]);
+}
+
+#[cfg(feature = "with-tokio")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_context_follows_future() {
+    use datadog_logs::logger::WithContextExt;
+
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .client(utils::DataDogClientStub::new(sender))
+        .build_with_tokio()
+        .unwrap();
+
+    async {
+        logger.log("before", DataDogLogLevel::Informational);
+        tokio::task::yield_now().await;
+        logger.log("after", DataDogLogLevel::Informational);
+    }
+    .with_context([("request_id", "42")])
+    .await;
+    logger.log("outside", DataDogLogLevel::Informational);
+
+    log::Log::flush(&logger);
+    let messages: Vec<DataDogLog> = receiver.try_iter().collect();
+    assert_eq!(3, messages.len());
+    assert_eq!("42", messages[0].attributes["request_id"]);
+    assert_eq!("42", messages[1].attributes["request_id"]);
+    assert!(!messages[2].attributes.contains_key("request_id"));
+}
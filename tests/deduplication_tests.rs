@@ -0,0 +1,107 @@
+mod utils;
+
+use datadog_logs::{
+    config::DataDogDeduplicationConfig,
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger},
+};
+use flume::{unbounded, Receiver};
+use std::time::Duration;
+
+fn create_logger(config: DataDogDeduplicationConfig) -> (DataDogLogger, Receiver<DataDogLog>) {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .channel_capacity(None)
+        .deduplicate(config)
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+    (logger, receiver)
+}
+
+#[test]
+fn test_repeated_logs_are_collapsed() {
+    let (logger, receiver) = create_logger(DataDogDeduplicationConfig {
+        window_ms: 60_000,
+        ..Default::default()
+    });
+
+    for _ in 0..1000 {
+        logger.log("connection refused", DataDogLogLevel::Error);
+    }
+    logger.log("connection refused", DataDogLogLevel::Warning);
+    logger.log("recovered", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!(3, messages.len());
+
+    assert_eq!("connection refused", messages[0].message);
+    assert_eq!(1000, messages[0].attributes["repeat_count"]);
+    let first = messages[0].attributes["first_timestamp"].as_str().unwrap();
+    let last = messages[0].attributes["last_timestamp"].as_str().unwrap();
+    assert!(first <= last);
+
+    assert_eq!("warning", messages[1].level);
+    assert!(!messages[1].attributes.contains_key("repeat_count"));
+    assert!(!messages[2].attributes.contains_key("repeat_count"));
+}
+
+#[test]
+fn test_logs_are_released_when_window_ends() {
+    let (logger, receiver) = create_logger(DataDogDeduplicationConfig {
+        window_ms: 100,
+        ..Default::default()
+    });
+
+    for _ in 0..3 {
+        logger.log("timeout", DataDogLogLevel::Error);
+    }
+
+    // released by logger thread while logger is still alive
+    let log = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(3, log.attributes["repeat_count"]);
+
+    logger.log("timeout", DataDogLogLevel::Error);
+    let log = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(!log.attributes.contains_key("repeat_count"));
+}
+
+#[test]
+fn test_key_fields_are_configurable() {
+    let (logger, receiver) = create_logger(DataDogDeduplicationConfig {
+        window_ms: 60_000,
+        key_fields: vec!["level".into()],
+    });
+
+    logger.log("first", DataDogLogLevel::Error);
+    logger.log("second", DataDogLogLevel::Error);
+    logger.log("third", DataDogLogLevel::Debug);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!(2, messages.len());
+    assert_eq!("first", messages[0].message);
+    assert_eq!(2, messages[0].attributes["repeat_count"]);
+    assert_eq!("third", messages[1].message);
+}
+
+#[cfg(feature = "with-tokio")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_nonblocking_logs_are_released_when_window_ends() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .deduplicate(DataDogDeduplicationConfig {
+            window_ms: 100,
+            ..Default::default()
+        })
+        .client(utils::DataDogClientStub::new(sender))
+        .build_with_tokio()
+        .unwrap();
+
+    for _ in 0..3 {
+        logger.log("timeout", DataDogLogLevel::Error);
+    }
+
+    let log = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(3, log.attributes["repeat_count"]);
+}
@@ -0,0 +1,145 @@
+mod utils;
+
+use datadog_logs::{
+    client::HttpDataDogClient,
+    config::{DataDogConfig, DataDogHttpConfig},
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger},
+};
+use std::time::Duration;
+use utils::http::HttpStandIn;
+
+fn config(primary: &HttpStandIn, secondary: &HttpStandIn) -> DataDogConfig {
+    DataDogConfig {
+        apikey: "failover-key".into(),
+        enable_self_log: true,
+        max_retries: 3,
+        retry_backoff_ms: 10,
+        http_config: DataDogHttpConfig {
+            url: primary.url.clone(),
+            fallback_urls: vec![secondary.url.clone()],
+            failover_threshold: 2,
+            failback_probe_interval_ms: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn messages(stand_in: &HttpStandIn) -> Vec<String> {
+    stand_in
+        .requests
+        .try_iter()
+        .flat_map(|request| serde_json::from_str::<Vec<DataDogLog>>(&request.body).unwrap())
+        .map(|log| log.message)
+        .collect()
+}
+
+#[test]
+fn test_logs_fail_over_to_secondary_and_back_to_primary() {
+    let primary = HttpStandIn::start();
+    let secondary = HttpStandIn::start();
+    primary.set_status(500);
+
+    let client = HttpDataDogClient::new(&config(&primary, &secondary)).unwrap();
+    let logger = DataDogLogger::blocking(client, config(&primary, &secondary));
+
+    logger.log("during outage", DataDogLogLevel::Error);
+    log::Log::flush(&logger);
+
+    // primary fails twice before client switches to secondary
+    assert_eq!(vec!["during outage"; 2], messages(&primary));
+    assert_eq!(vec!["during outage"], messages(&secondary));
+
+    primary.set_status(200);
+    std::thread::sleep(Duration::from_millis(300));
+    logger.log("after recovery", DataDogLogLevel::Informational);
+    log::Log::flush(&logger);
+    logger.log("back on primary", DataDogLogLevel::Informational);
+    log::Log::flush(&logger);
+
+    assert_eq!(
+        vec!["after recovery", "back on primary"],
+        messages(&primary)
+    );
+    assert!(messages(&secondary).is_empty());
+
+    let selflog: Vec<String> = logger.selflog().as_ref().unwrap().try_iter().collect();
+    assert!(selflog.iter().any(|message| message
+        == &format!(
+            "Switching from {} to {} after 2 consecutive failures",
+            primary.url, secondary.url
+        )));
+    assert!(selflog
+        .iter()
+        .any(|message| message == &format!("Failing back to {}", primary.url)));
+}
+
+#[test]
+fn test_client_keeps_primary_without_fallback_urls() {
+    let primary = HttpStandIn::start();
+    let secondary = HttpStandIn::start();
+    primary.set_status(500);
+
+    let mut config = config(&primary, &secondary);
+    config.http_config.fallback_urls.clear();
+    let client = HttpDataDogClient::new(&config).unwrap();
+    let logger = DataDogLogger::blocking(client, config);
+
+    logger.log("no fallback", DataDogLogLevel::Error);
+    log::Log::flush(&logger);
+
+    assert_eq!(vec!["no fallback"; 4], messages(&primary));
+    let selflog: Vec<String> = logger.selflog().as_ref().unwrap().try_iter().collect();
+    assert!(!selflog
+        .iter()
+        .any(|message| message.starts_with("Switching")));
+}
+
+#[test]
+fn test_client_errors_do_not_fail_over() {
+    let primary = HttpStandIn::start();
+    let secondary = HttpStandIn::start();
+    primary.set_status(403);
+
+    let client = HttpDataDogClient::new(&config(&primary, &secondary)).unwrap();
+    let logger = DataDogLogger::blocking(client, config(&primary, &secondary));
+
+    logger.log("rejected", DataDogLogLevel::Error);
+    log::Log::flush(&logger);
+
+    assert_eq!(vec!["rejected"; 4], messages(&primary));
+    assert!(messages(&secondary).is_empty());
+}
+
+#[test]
+fn test_throttling_fails_over() {
+    let primary = HttpStandIn::start();
+    let secondary = HttpStandIn::start();
+    primary.set_status(429);
+
+    let client = HttpDataDogClient::new(&config(&primary, &secondary)).unwrap();
+    let logger = DataDogLogger::blocking(client, config(&primary, &secondary));
+
+    logger.log("throttled", DataDogLogLevel::Error);
+    log::Log::flush(&logger);
+
+    assert_eq!(vec!["throttled"; 2], messages(&primary));
+    assert_eq!(vec!["throttled"], messages(&secondary));
+}
+
+#[cfg(feature = "with-tokio")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_async_logs_fail_over_to_secondary() {
+    let primary = HttpStandIn::start();
+    let secondary = HttpStandIn::start();
+    primary.set_status(500);
+
+    let client = HttpDataDogClient::new(&config(&primary, &secondary)).unwrap();
+    let logger = DataDogLogger::non_blocking_with_tokio(client, config(&primary, &secondary));
+
+    logger.log("during outage", DataDogLogLevel::Error);
+    log::Log::flush(&logger);
+
+    assert_eq!(vec!["during outage"; 2], messages(&primary));
+    assert_eq!(vec!["during outage"], messages(&secondary));
+}
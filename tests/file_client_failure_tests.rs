@@ -0,0 +1,71 @@
+#![cfg(unix)]
+//! Separate binary, since file size limit applies to the whole process
+
+use datadog_logs::{
+    client::{DataDogClient, FileDataDogClient},
+    config::{DataDogConfig, DataDogFileConfig},
+    logger::DataDogLog,
+};
+use std::fs;
+
+fn log(message: &str) -> DataDogLog {
+    DataDogLog {
+        message: message.into(),
+        ddtags: Default::default(),
+        ddsource: "rust".into(),
+        host: "host".into(),
+        service: "service".into(),
+        env: None,
+        version: None,
+        level: "info".into(),
+        attributes: Default::default(),
+    }
+}
+
+fn set_file_size_limit(limit: libc::rlim_t) {
+    let limit = libc::rlimit {
+        rlim_cur: limit,
+        rlim_max: libc::RLIM_INFINITY,
+    };
+    assert_eq!(0, unsafe { libc::setrlimit(libc::RLIMIT_FSIZE, &limit) });
+}
+
+#[test]
+fn test_partially_written_batch_is_not_duplicated_on_retry() {
+    let directory =
+        std::env::temp_dir().join(format!("datadog-logs-{}-partial", std::process::id()));
+    fs::remove_dir_all(&directory).unwrap_or_default();
+    fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("app.log");
+    let mut client = FileDataDogClient::new(&DataDogConfig {
+        file_config: DataDogFileConfig {
+            path: path.to_string_lossy().into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap();
+
+    client.send(&[log("first")]).unwrap();
+    let written = fs::metadata(&path).unwrap().len();
+    let batch: Vec<DataDogLog> = (0..10).map(|i| log(&format!("second {}", i))).collect();
+
+    // writes past the limit fail with EFBIG instead of killing the process
+    unsafe { libc::signal(libc::SIGXFSZ, libc::SIG_IGN) };
+    set_file_size_limit(written + 300);
+    let result = client.send(&batch);
+    set_file_size_limit(libc::RLIM_INFINITY);
+
+    assert!(result.is_err());
+    assert_eq!(written, fs::metadata(&path).unwrap().len());
+
+    client.send(&batch).unwrap();
+    let messages: Vec<String> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<DataDogLog>(line).unwrap().message)
+        .collect();
+    assert_eq!(11, messages.len());
+    assert_eq!("first", messages[0]);
+    assert_eq!("second 9", messages[10]);
+}
@@ -0,0 +1,159 @@
+use datadog_logs::{
+    client::{DataDogClient, FileDataDogClient},
+    config::{DataDogConfig, DataDogFileConfig, DataDogFsyncPolicy},
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger},
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Creates empty directory unique for a test
+fn directory(test: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("datadog-logs-{}-{}", std::process::id(), test));
+    fs::remove_dir_all(&directory).unwrap_or_default();
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn client(file_config: DataDogFileConfig) -> FileDataDogClient {
+    FileDataDogClient::new(&DataDogConfig {
+        file_config,
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+fn log(message: &str) -> DataDogLog {
+    DataDogLog {
+        message: message.into(),
+        ddtags: Default::default(),
+        ddsource: "rust".into(),
+        host: "host".into(),
+        service: "service".into(),
+        env: None,
+        version: None,
+        level: "info".into(),
+        attributes: Default::default(),
+    }
+}
+
+fn messages(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<DataDogLog>(line).unwrap().message)
+        .collect()
+}
+
+#[test]
+fn test_logs_are_written_as_json_lines() {
+    let directory = directory("json-lines");
+    let path = directory.join("app.log");
+
+    let logger = DataDogLogger::builder()
+        .config(DataDogConfig {
+            file_config: DataDogFileConfig {
+                path: path.to_string_lossy().into(),
+                fsync: DataDogFsyncPolicy::EveryBatch,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .file()
+        .build_blocking()
+        .unwrap();
+    logger.log("first", DataDogLogLevel::Informational);
+    logger.log("second", DataDogLogLevel::Error);
+    std::mem::drop(logger);
+
+    assert_eq!(vec!["first", "second"], messages(&path));
+    let line = fs::read_to_string(&path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(line.lines().next().unwrap()).unwrap();
+    assert_eq!("info", json["level"]);
+}
+
+#[test]
+fn test_file_is_rotated_by_size() {
+    let directory = directory("rotation");
+    let path = directory.join("app.log");
+    let mut client = client(DataDogFileConfig {
+        path: path.to_string_lossy().into(),
+        max_size_bytes: Some(150),
+        max_files: 2,
+        ..Default::default()
+    });
+
+    for message in ["1", "2", "3", "4"] {
+        client.send(&[log(message)]).unwrap();
+    }
+
+    assert_eq!(vec!["4"], messages(&path));
+    assert_eq!(vec!["3"], messages(&directory.join("app.log.1")));
+    assert_eq!(vec!["2"], messages(&directory.join("app.log.2")));
+    assert!(!directory.join("app.log.3").exists());
+}
+
+#[test]
+fn test_file_is_rotated_by_time() {
+    let directory = directory("time-rotation");
+    let path = directory.join("app.log");
+    let mut client = client(DataDogFileConfig {
+        path: path.to_string_lossy().into(),
+        rotation_interval_ms: Some(50),
+        ..Default::default()
+    });
+
+    client.send(&[log("old"), log("older")]).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    client.send(&[log("new")]).unwrap();
+
+    assert_eq!(vec!["new"], messages(&path));
+    assert_eq!(vec!["old", "older"], messages(&directory.join("app.log.1")));
+}
+
+// open files cannot be moved on Windows
+#[cfg(unix)]
+#[test]
+fn test_file_is_reopened_after_external_rotation() {
+    let directory = directory("external-rotation");
+    let path = directory.join("app.log");
+    let mut client = client(DataDogFileConfig {
+        path: path.to_string_lossy().into(),
+        ..Default::default()
+    });
+
+    client.send(&[log("before")]).unwrap();
+    fs::rename(&path, directory.join("moved.log")).unwrap();
+    client.send(&[log("after")]).unwrap();
+
+    assert_eq!(vec!["before"], messages(&directory.join("moved.log")));
+    assert_eq!(vec!["after"], messages(&path));
+
+    // file replaced with another one under the same name, e.g. by copy-truncate-less rotation
+    fs::remove_file(&path).unwrap();
+    fs::write(&path, "").unwrap();
+    client.send(&[log("replaced")]).unwrap();
+    assert_eq!(vec!["replaced"], messages(&path));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_is_reopened_on_request() {
+    let directory = directory("reopen");
+    let path = directory.join("app.log");
+    let mut client = client(DataDogFileConfig {
+        path: path.to_string_lossy().into(),
+        ..Default::default()
+    });
+    let handle = client.reopen_handle();
+
+    client.send(&[log("before")]).unwrap();
+    fs::rename(&path, directory.join("moved.log")).unwrap();
+    fs::write(&path, "").unwrap();
+    handle.reopen();
+    client.send(&[log("after")]).unwrap();
+
+    assert_eq!(vec!["after"], messages(&path));
+}
@@ -0,0 +1,144 @@
+mod utils;
+
+use datadog_logs::{
+    client::{DataDogClient, HttpDataDogClient},
+    config::{DataDogConfig, DataDogHeaderHook},
+    error::DataDogLoggerError,
+    logger::{DataDogLogLevel, DataDogLogger},
+};
+use std::time::Duration;
+use utils::http::{HttpRequest, HttpStandIn};
+
+fn config(stand_in: &HttpStandIn) -> DataDogConfig {
+    let mut config = DataDogConfig {
+        apikey: "header-key".into(),
+        ..Default::default()
+    };
+    config.http_config.url = stand_in.url.clone();
+    config
+}
+
+fn received(stand_in: &HttpStandIn) -> HttpRequest {
+    stand_in
+        .requests
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap()
+}
+
+fn count(request: &HttpRequest, name: &str) -> usize {
+    request
+        .headers
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+        .count()
+}
+
+#[test]
+fn test_requests_carry_static_hook_and_default_headers() {
+    let stand_in = HttpStandIn::start();
+    let logger = DataDogLogger::builder()
+        .config(config(&stand_in))
+        .header("X-Tenant-Id", "tenant-42")
+        .header_hook(|request| vec![("X-Body-Length".to_string(), request.body.len().to_string())])
+        .http()
+        .build_blocking()
+        .unwrap();
+
+    logger.log("with headers", DataDogLogLevel::Informational);
+    log::Log::flush(&logger);
+
+    let request = received(&stand_in);
+    assert_eq!(Some("tenant-42"), request.header("X-Tenant-Id"));
+    assert_eq!(
+        Some(request.body.len().to_string().as_str()),
+        request.header("X-Body-Length")
+    );
+    assert_eq!(Some("header-key"), request.header("DD-API-KEY"));
+    assert_eq!(
+        Some(concat!("datadog-logs/", env!("CARGO_PKG_VERSION"))),
+        request.header("User-Agent")
+    );
+    assert_eq!(1, count(&request, "User-Agent"));
+}
+
+#[test]
+fn test_configured_headers_replace_default_ones() {
+    let stand_in = HttpStandIn::start();
+    let mut config = config(&stand_in);
+    config
+        .http_config
+        .headers
+        .insert("user-agent".into(), "billing-service/1.2".into());
+    config.http_config.header_hook = Some(DataDogHeaderHook::new(|_| {
+        vec![("X-Tenant-Id".to_string(), "from-hook".to_string())]
+    }));
+    config
+        .http_config
+        .headers
+        .insert("X-Tenant-Id".into(), "static".into());
+    let mut client = HttpDataDogClient::new(&config).unwrap();
+
+    client.send(&[]).unwrap();
+
+    let request = received(&stand_in);
+    assert_eq!(Some("billing-service/1.2"), request.header("User-Agent"));
+    assert_eq!(1, count(&request, "User-Agent"));
+    assert_eq!(Some("from-hook"), request.header("X-Tenant-Id"));
+    assert_eq!(1, count(&request, "X-Tenant-Id"));
+}
+
+#[test]
+fn test_invalid_headers_are_rejected() {
+    let stand_in = HttpStandIn::start();
+
+    let mut invalid_static = config(&stand_in);
+    invalid_static
+        .http_config
+        .headers
+        .insert("X-Tenant-Id".into(), "tenant\r\nX-Injected: yes".into());
+    assert!(matches!(
+        HttpDataDogClient::new(&invalid_static),
+        Err(DataDogLoggerError::ConfigError(_))
+    ));
+
+    let mut invalid_hook = config(&stand_in);
+    invalid_hook.http_config.header_hook = Some(DataDogHeaderHook::new(|_| {
+        vec![("Bad Name".to_string(), "value".to_string())]
+    }));
+    let mut client = HttpDataDogClient::new(&invalid_hook).unwrap();
+    assert!(client.send(&[]).is_err());
+    assert!(stand_in
+        .requests
+        .recv_timeout(Duration::from_millis(200))
+        .is_err());
+}
+
+#[cfg(feature = "nonblocking-core")]
+#[tokio::test]
+async fn test_async_requests_carry_headers() {
+    use datadog_logs::client::AsyncDataDogClient;
+
+    let stand_in = HttpStandIn::start();
+    let mut config = config(&stand_in);
+    config
+        .http_config
+        .headers
+        .insert("X-Tenant-Id".into(), "tenant-42".into());
+    config.http_config.header_hook = Some(DataDogHeaderHook::new(|_| {
+        vec![(
+            "Authorization".to_string(),
+            "Bearer short-lived".to_string(),
+        )]
+    }));
+    let mut client = HttpDataDogClient::new(&config).unwrap();
+
+    client.send_async(&[]).await.unwrap();
+
+    let request = received(&stand_in);
+    assert_eq!(Some("tenant-42"), request.header("X-Tenant-Id"));
+    assert_eq!(Some("Bearer short-lived"), request.header("Authorization"));
+    assert_eq!(
+        Some(concat!("datadog-logs/", env!("CARGO_PKG_VERSION"))),
+        request.header("User-Agent")
+    );
+}
@@ -0,0 +1,59 @@
+mod utils;
+
+use datadog_logs::logger::{DataDogLog, DataDogLogLevel, DataDogLogger};
+use flume::unbounded;
+
+#[test]
+fn test_hostname_is_resolved_once_when_logger_is_created() {
+    let (sender, receiver) = unbounded();
+    let (from_dd_hostname, explicit, disabled) = utils::env::with_vars(
+        &[
+            ("DD_HOSTNAME", Some("dd-host")),
+            ("POD_NAME", Some("pod-1")),
+        ],
+        || {
+            let from_dd_hostname = DataDogLogger::builder()
+                .client(utils::DataDogClientStub::new(sender.clone()))
+                .build_blocking()
+                .unwrap();
+            let explicit = DataDogLogger::builder()
+                .hostname("explicit")
+                .client(utils::DataDogClientStub::new(sender.clone()))
+                .build_blocking()
+                .unwrap();
+            let disabled = DataDogLogger::builder()
+                .detect_hostname(false)
+                .client(utils::DataDogClientStub::new(sender.clone()))
+                .build_blocking()
+                .unwrap();
+            (from_dd_hostname, explicit, disabled)
+        },
+    );
+    let from_pod_name = utils::env::with_vars(
+        &[("DD_HOSTNAME", None), ("POD_NAME", Some("pod-1"))],
+        || {
+            DataDogLogger::builder()
+                .client(utils::DataDogClientStub::new(sender))
+                .build_blocking()
+                .unwrap()
+        },
+    );
+
+    assert_eq!(
+        Some("dd-host"),
+        from_dd_hostname.config().hostname.as_deref()
+    );
+    assert_eq!(Some("explicit"), explicit.config().hostname.as_deref());
+    assert_eq!(None, disabled.config().hostname);
+    assert_eq!(Some("pod-1"), from_pod_name.config().hostname.as_deref());
+
+    for logger in [from_dd_hostname, explicit, disabled, from_pod_name] {
+        logger.log("message", DataDogLogLevel::Informational);
+    }
+
+    let hosts: Vec<String> = receiver
+        .iter()
+        .map(|log: DataDogLog| log.host.to_string())
+        .collect();
+    assert_eq!(vec!["dd-host", "explicit", "", "pod-1"], hosts);
+}
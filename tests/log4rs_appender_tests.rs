@@ -0,0 +1,83 @@
+#![cfg(feature = "log4rs")]
+mod utils;
+
+use datadog_logs::logger::{DataDogAppender, DataDogAppenderDeserializer, DataDogLogger};
+use flume::unbounded;
+use log::{Level, Record};
+use log4rs::{
+    append::Append,
+    config::{Deserializers, RawConfig},
+};
+use serde_json::Value;
+use std::time::Duration;
+
+#[test]
+fn test_appender_sends_records() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+    let appender = DataDogAppender::new(logger);
+
+    appender
+        .append(
+            &Record::builder()
+                .args(format_args!("appended"))
+                .level(Level::Warn)
+                .build(),
+        )
+        .unwrap();
+    appender.flush();
+
+    let messages: Vec<String> = receiver.try_iter().map(|log| log.message).collect();
+    assert_eq!(vec!["appended"], messages);
+}
+
+#[test]
+fn test_appender_is_deserialized_from_yaml() {
+    let stand_in = utils::http::HttpStandIn::start();
+    let yaml = format!(
+        r#"
+appenders:
+  datadog:
+    kind: datadog
+    apikey: yaml-key
+    service: yaml-service
+    batch_size: 10
+    http_config:
+      url: {}
+root:
+  level: info
+  appenders:
+    - datadog
+"#,
+        stand_in.url
+    );
+    let config: RawConfig = serde_yaml::from_str(&yaml).unwrap();
+    let mut deserializers = Deserializers::default();
+    deserializers.insert("datadog", DataDogAppenderDeserializer);
+
+    let (appenders, errors) = config.appenders_lossy(&deserializers);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let appender = appenders[0].appender();
+
+    appender
+        .append(
+            &Record::builder()
+                .args(format_args!("from yaml"))
+                .level(Level::Error)
+                .build(),
+        )
+        .unwrap();
+    appender.flush();
+
+    let request = stand_in
+        .requests
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(Some("yaml-key"), request.header("DD-API-KEY"));
+    let body: Value = serde_json::from_str(&request.body).unwrap();
+    assert_eq!("from yaml", body[0]["message"]);
+    assert_eq!("yaml-service", body[0]["service"]);
+}
@@ -0,0 +1,205 @@
+mod utils;
+
+use datadog_logs::{
+    client::{DataDogClient, Destination, MultiDataDogClient},
+    config::DataDogConfig,
+    error::DataDogLoggerError,
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger},
+};
+use flume::{unbounded, Sender};
+use log::{Level, LevelFilter, Log, Record};
+use std::time::Duration;
+
+/// Client failing given number of times before it starts sending logs
+struct FlakyClient {
+    failures: usize,
+    sender: Sender<DataDogLog>,
+}
+
+impl DataDogClient for FlakyClient {
+    fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        if self.failures > 0 {
+            self.failures -= 1;
+            return Err(DataDogLoggerError::OtherError("temporary failure".into()));
+        }
+        for message in messages {
+            self.sender.send(message.clone()).unwrap_or_default();
+        }
+        Ok(())
+    }
+}
+
+/// Client that takes long to send each batch
+struct SlowClient(utils::DataDogClientStub);
+
+impl DataDogClient for SlowClient {
+    fn send(&mut self, messages: &[DataDogLog]) -> Result<(), DataDogLoggerError> {
+        std::thread::sleep(Duration::from_millis(500));
+        self.0.send(messages)
+    }
+}
+
+fn log(logger: &DataDogLogger, level: Level, target: &str, message: &str) {
+    Log::log(
+        logger,
+        &Record::builder()
+            .args(format_args!("{}", message))
+            .level(level)
+            .target(target)
+            .build(),
+    );
+}
+
+#[test]
+fn test_destinations_receive_logs_passing_their_filters() {
+    let (all_sender, all) = unbounded();
+    let (warnings_sender, warnings) = unbounded();
+    let (database_sender, database) = unbounded();
+    let client = MultiDataDogClient::new()
+        .destination(Destination::new(
+            "all",
+            utils::DataDogClientStub::new(all_sender),
+        ))
+        .destination(
+            Destination::new("warnings", utils::DataDogClientStub::new(warnings_sender))
+                .level(LevelFilter::Warn),
+        )
+        .destination(
+            Destination::new("database", utils::DataDogClientStub::new(database_sender))
+                .level(LevelFilter::Off)
+                .filter("app::db", LevelFilter::Debug),
+        );
+
+    let logger = DataDogLogger::blocking(client, DataDogConfig::default());
+    log(&logger, Level::Info, "app::http", "request");
+    log(&logger, Level::Error, "app::http", "failure");
+    log(&logger, Level::Debug, "app::db::pool", "query");
+    logger.log("without target", DataDogLogLevel::Critical);
+    std::mem::drop(logger);
+
+    let messages = |receiver: flume::Receiver<DataDogLog>| -> Vec<String> {
+        receiver.iter().map(|log| log.message).collect()
+    };
+    assert_eq!(
+        vec!["request", "failure", "query", "without target"],
+        messages(all)
+    );
+    assert_eq!(vec!["failure", "without target"], messages(warnings));
+    assert_eq!(vec!["query"], messages(database));
+}
+
+#[test]
+fn test_failing_and_slow_destinations_do_not_affect_others() {
+    let (healthy_sender, healthy) = unbounded();
+    let (slow_sender, slow) = unbounded();
+    let (failing_sender, _failing) = unbounded();
+    let mut failing = utils::DataDogClientStub::new(failing_sender);
+    failing.should_error = true;
+
+    let client = MultiDataDogClient::new()
+        .destination(Destination::new("failing", failing).retry(1, Duration::from_millis(1)))
+        .destination(Destination::new(
+            "slow",
+            SlowClient(utils::DataDogClientStub::new(slow_sender)),
+        ))
+        .destination(Destination::new(
+            "healthy",
+            utils::DataDogClientStub::new(healthy_sender),
+        ));
+
+    let logger = DataDogLogger::blocking(
+        client,
+        DataDogConfig {
+            enable_self_log: true,
+            ..Default::default()
+        },
+    );
+    let selflog = logger.selflog().clone().unwrap();
+    logger.log("message", DataDogLogLevel::Error);
+
+    let received = healthy.recv_timeout(Duration::from_millis(300)).unwrap();
+    assert_eq!("message", received.message);
+    assert!(slow.is_empty());
+
+    std::mem::drop(logger);
+    assert_eq!(1, slow.len());
+
+    let errors: Vec<String> = selflog.try_iter().collect();
+    assert_eq!(3, errors.len());
+    assert!(errors.iter().all(|e| e.starts_with("failing : ")));
+    assert!(errors[2].ends_with("Dropping 1 logs after 2 failed attempts"));
+}
+
+#[test]
+fn test_destinations_retry_independently() {
+    let (healthy_sender, healthy) = unbounded();
+    let (flaky_sender, flaky) = unbounded();
+    let client = MultiDataDogClient::new()
+        .destination(
+            Destination::new(
+                "flaky",
+                FlakyClient {
+                    failures: 2,
+                    sender: flaky_sender,
+                },
+            )
+            .retry(3, Duration::from_millis(1)),
+        )
+        .destination(Destination::new(
+            "healthy",
+            utils::DataDogClientStub::new(healthy_sender),
+        ));
+
+    let logger = DataDogLogger::blocking(client, DataDogConfig::default());
+    logger.log("message", DataDogLogLevel::Error);
+    std::mem::drop(logger);
+
+    assert_eq!(1, healthy.len());
+    assert_eq!(1, flaky.len());
+}
+
+#[test]
+fn test_flush_waits_for_destinations() {
+    let (slow_sender, slow) = unbounded();
+    let client = MultiDataDogClient::new().destination(Destination::new(
+        "slow",
+        SlowClient(utils::DataDogClientStub::new(slow_sender)),
+    ));
+
+    let logger = DataDogLogger::blocking(client, DataDogConfig::default());
+    logger.log("message", DataDogLogLevel::Error);
+    Log::flush(&logger);
+
+    assert_eq!(1, slow.len());
+}
+
+#[test]
+fn test_dropped_batches_are_reported_to_self_log() {
+    let (slow_sender, _slow) = unbounded();
+    let client = MultiDataDogClient::new().destination(
+        Destination::new(
+            "slow",
+            SlowClient(utils::DataDogClientStub::new(slow_sender)),
+        )
+        .queue_capacity(1),
+    );
+
+    let logger = DataDogLogger::blocking(
+        client,
+        DataDogConfig {
+            enable_self_log: true,
+            batch_size: 1,
+            ..Default::default()
+        },
+    );
+    let selflog = logger.selflog().clone().unwrap();
+    for _ in 0..3 {
+        logger.log("message", DataDogLogLevel::Error);
+    }
+    std::mem::drop(logger);
+
+    let notices: Vec<String> = selflog.try_iter().collect();
+    assert!(notices
+        .iter()
+        .any(|notice| notice == "slow : Dropping 1 logs, queue is full"));
+}
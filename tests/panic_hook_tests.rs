@@ -0,0 +1,54 @@
+mod utils;
+
+use datadog_logs::logger::{DataDogLog, DataDogLogger};
+use flume::unbounded;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::{panic, thread};
+
+// panic hook is global, hence single test in this file
+#[test]
+fn test_panic_is_logged_and_flushed_before_previous_hook() {
+    let previous_called = Arc::new(AtomicBool::new(false));
+    let flag = previous_called.clone();
+    panic::set_hook(Box::new(move |_| flag.store(true, Ordering::SeqCst)));
+
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .batch_size(1000)
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+    logger.install_panic_hook();
+
+    let result = thread::Builder::new()
+        .name("doomed".into())
+        .spawn(|| panic!("boom"))
+        .unwrap()
+        .join();
+    assert!(result.is_err());
+    assert!(previous_called.load(Ordering::SeqCst));
+
+    // logger is still alive, so the log must have been flushed by the hook itself
+    let messages: Vec<DataDogLog> = receiver.try_iter().collect();
+    assert_eq!(1, messages.len());
+    let log = &messages[0];
+    assert_eq!("crit", log.level);
+    assert!(log
+        .message
+        .starts_with("thread 'doomed' panicked at tests/panic_hook_tests.rs:"));
+    assert!(log.message.ends_with(": boom"));
+    assert_eq!("panic", log.attributes["error"]["kind"]);
+    assert_eq!("boom", log.attributes["error"]["message"]);
+    assert!(!log.attributes["error"]["stack"]
+        .as_str()
+        .unwrap()
+        .is_empty());
+    assert_eq!("doomed", log.attributes["logger"]["thread_name"]);
+
+    // hook does not prevent logger from shutting down
+    std::mem::drop(logger);
+    assert!(receiver.recv().is_err());
+}
@@ -0,0 +1,102 @@
+mod utils;
+
+use datadog_logs::{
+    config::DataDogRedactionRule,
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger},
+    processor::{
+        EnvironmentTagProcessor, HostnameProcessor, RenameFieldProcessor, StaticAttributesProcessor,
+    },
+};
+use flume::unbounded;
+use log::{Log, Record};
+use serde_json::json;
+
+#[test]
+fn test_processors_run_in_order() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .tags("team:core".parse().unwrap())
+        .detect_hostname(false)
+        .processor(StaticAttributesProcessor::new().attribute("region", "eu"))
+        .processor(HostnameProcessor::new("machine-1"))
+        .processor(EnvironmentTagProcessor::new("prod"))
+        .processor(RenameFieldProcessor::new("region", "geo.region"))
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log("started", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!(1, messages.len());
+    assert_eq!("machine-1", &*messages[0].host);
+    assert_eq!("team:core,env:prod", messages[0].ddtags.to_string());
+    assert_eq!(json!({"region": "eu"}), messages[0].attributes["geo"]);
+    assert!(!messages[0].attributes.contains_key("region"));
+}
+
+#[test]
+fn test_processor_does_not_overwrite_existing_values() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .hostname("configured")
+        .processor(HostnameProcessor::new("machine-1"))
+        .processor(StaticAttributesProcessor::new().attribute("logger", "static"))
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    Log::log(
+        &logger,
+        &Record::builder()
+            .args(format_args!("message"))
+            .target("app::db")
+            .build(),
+    );
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!("configured", &*messages[0].host);
+    assert_eq!(json!({"name": "app::db"}), messages[0].attributes["logger"]);
+}
+
+#[test]
+fn test_closure_processor_drops_logs() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .processor(|log: DataDogLog| {
+            if log.message.contains("/health") {
+                None
+            } else {
+                Some(log)
+            }
+        })
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log("GET /health", DataDogLogLevel::Informational);
+    logger.log("GET /orders", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let messages: Vec<String> = receiver.iter().map(|log| log.message).collect();
+    assert_eq!(vec!["GET /orders".to_string()], messages);
+}
+
+#[test]
+fn test_attributes_added_by_processors_are_redacted() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .redact(DataDogRedactionRule::new("secret-[0-9]+"))
+        .processor(StaticAttributesProcessor::new().attribute("token", "secret-42"))
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log("message", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!("[REDACTED]", messages[0].attributes["token"]);
+}
@@ -16,6 +16,7 @@ fn test_logger_stops_http() {
     std::mem::drop(logger);
 }
 
+#[cfg(feature = "nonblocking-core")]
 #[tokio::test]
 async fn test_async_logger_stops_http() {
     let config = DataDogConfig::default();
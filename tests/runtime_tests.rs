@@ -0,0 +1,134 @@
+#![cfg(any(feature = "with-async-std", feature = "with-smol"))]
+mod utils;
+
+use datadog_logs::{
+    client::{DefaultHttpTransport, HttpDataDogClient, UnblockHttpTransport},
+    config::DataDogConfig,
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger},
+};
+use std::time::Duration;
+use utils::http::HttpStandIn;
+
+type Client = HttpDataDogClient<UnblockHttpTransport<DefaultHttpTransport>>;
+
+/// Client sending requests on a thread pool rather than with reqwest or hyper, which would require tokio
+fn client(stand_in: &HttpStandIn) -> (Client, DataDogConfig) {
+    let mut config = DataDogConfig {
+        apikey: "runtime-key".into(),
+        ..Default::default()
+    };
+    config.http_config.url = stand_in.url.clone();
+    let transport =
+        UnblockHttpTransport::new(DefaultHttpTransport::new(&config.http_config).unwrap());
+    (
+        HttpDataDogClient::with_transport(&config, transport).unwrap(),
+        config,
+    )
+}
+
+fn received_messages(stand_in: &HttpStandIn) -> Vec<String> {
+    let request = stand_in
+        .requests
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap();
+    let logs: Vec<DataDogLog> = serde_json::from_str(&request.body).unwrap();
+    logs.into_iter().map(|log| log.message).collect()
+}
+
+#[cfg(feature = "with-async-std")]
+#[test]
+fn test_logger_runs_on_async_std() {
+    let stand_in = HttpStandIn::start();
+    let (client, config) = client(&stand_in);
+
+    async_std::task::block_on(async {
+        let logger = DataDogLogger::non_blocking_with_async_std(client, config);
+        logger.log("on async-std", DataDogLogLevel::Informational);
+        log::Log::flush(&logger);
+    });
+
+    assert_eq!(vec!["on async-std"], received_messages(&stand_in));
+}
+
+#[cfg(feature = "with-smol")]
+#[test]
+fn test_logger_runs_on_smol() {
+    let stand_in = HttpStandIn::start();
+    let (client, config) = client(&stand_in);
+
+    smol::block_on(async {
+        let logger = DataDogLogger::non_blocking_with_smol(client, config);
+        logger.log("on smol", DataDogLogLevel::Informational);
+        log::Log::flush(&logger);
+    });
+
+    assert_eq!(vec!["on smol"], received_messages(&stand_in));
+}
+
+#[cfg(feature = "with-smol")]
+#[test]
+fn test_builder_spawns_logger_on_smol() {
+    let stand_in = HttpStandIn::start();
+    let (client, config) = client(&stand_in);
+
+    smol::block_on(async {
+        let logger = DataDogLogger::builder()
+            .config(config)
+            .client(client)
+            .build_with_smol()
+            .unwrap();
+        logger.log("built for smol", DataDogLogLevel::Informational);
+        log::Log::flush(&logger);
+    });
+
+    assert_eq!(vec!["built for smol"], received_messages(&stand_in));
+}
+
+/// Configuration of default HTTP client, which would use reqwest or hyper with tokio-based logger
+#[cfg(any(feature = "reqwest", feature = "hyper"))]
+fn default_client_config(stand_in: &HttpStandIn) -> DataDogConfig {
+    let mut config = DataDogConfig {
+        apikey: "runtime-key".into(),
+        ..Default::default()
+    };
+    config.http_config.url = stand_in.url.clone();
+    config
+}
+
+#[cfg(all(
+    feature = "with-async-std",
+    any(feature = "reqwest", feature = "hyper")
+))]
+#[test]
+fn test_builder_sends_requests_on_thread_pool_on_async_std_despite_tokio_transports() {
+    let stand_in = HttpStandIn::start();
+
+    async_std::task::block_on(async {
+        let logger = DataDogLogger::builder()
+            .config(default_client_config(&stand_in))
+            .build_with_async_std()
+            .unwrap();
+        logger.log("built for async-std", DataDogLogLevel::Informational);
+        log::Log::flush(&logger);
+    });
+
+    assert_eq!(vec!["built for async-std"], received_messages(&stand_in));
+}
+
+#[cfg(all(feature = "with-smol", any(feature = "reqwest", feature = "hyper")))]
+#[test]
+fn test_builder_sends_requests_on_thread_pool_on_smol_despite_tokio_transports() {
+    let stand_in = HttpStandIn::start();
+
+    smol::block_on(async {
+        let logger = DataDogLogger::builder()
+            .config(default_client_config(&stand_in))
+            .http()
+            .build_with_smol()
+            .unwrap();
+        logger.log("built for smol", DataDogLogLevel::Informational);
+        log::Log::flush(&logger);
+    });
+
+    assert_eq!(vec!["built for smol"], received_messages(&stand_in));
+}
@@ -0,0 +1,127 @@
+mod utils;
+
+use datadog_logs::{
+    config::DataDogSamplingRule,
+    error::DataDogLoggerError,
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger},
+};
+use flume::unbounded;
+use log::{Level, Log, Record};
+
+#[test]
+fn test_rules_match_level_and_target() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .sample(
+            DataDogSamplingRule::new(0.0)
+                .target("http::access")
+                .level(DataDogLogLevel::Informational),
+        )
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    let log = |target: &str, level: Level| {
+        Log::log(
+            &logger,
+            &Record::builder()
+                .args(format_args!("{} {}", target, level))
+                .target(target)
+                .level(level)
+                .build(),
+        )
+    };
+    log("http::access", Level::Info);
+    log("http::access::inner", Level::Debug);
+    log("http::access", Level::Warn);
+    log("http", Level::Info);
+    log("http::accessor", Level::Info);
+
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    let texts: Vec<&str> = messages.iter().map(|log| log.message.as_str()).collect();
+    assert_eq!(
+        vec!["http::access WARN", "http INFO", "http::accessor INFO"],
+        texts
+    );
+    // logs not matching any rule are not stamped
+    assert!(messages
+        .iter()
+        .all(|log| !log.attributes.contains_key("sample_rate")));
+}
+
+#[test]
+fn test_random_sampling_keeps_fraction_of_logs() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .channel_capacity(None)
+        .sample(DataDogSamplingRule::new(0.1))
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    for i in 0..10_000 {
+        logger.log(format!("message{}", i), DataDogLogLevel::Informational);
+    }
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert!(
+        (800..1200).contains(&messages.len()),
+        "kept {}",
+        messages.len()
+    );
+    assert!(messages
+        .iter()
+        .all(|log| log.attributes["sample_rate"] == 0.1));
+}
+
+#[test]
+fn test_invalid_sample_rate_fails_builder() {
+    let (sender, _receiver) = unbounded();
+    let result = DataDogLogger::builder()
+        .sample(DataDogSamplingRule::new(1.5))
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking();
+
+    assert!(matches!(result, Err(DataDogLoggerError::ConfigError(_))));
+}
+
+#[cfg(feature = "slog")]
+#[test]
+fn test_keyed_sampling_keeps_related_logs_together() {
+    use datadog_logs::logger::DataDogDrain;
+    use slog::{info, o, Drain, Logger};
+    use std::collections::HashMap;
+
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .channel_capacity(None)
+        .sample(DataDogSamplingRule::new(0.5).key("request_id"))
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+    let root = Logger::root(DataDogDrain::new(logger).fuse(), o!());
+
+    for request_id in 0..1000 {
+        let request = root.new(o!("request_id" => request_id));
+        for step in 0..3 {
+            info!(request, "step {}", step);
+        }
+    }
+    std::mem::drop(root);
+
+    let mut lines_per_request: HashMap<String, usize> = HashMap::new();
+    for log in receiver.iter() {
+        *lines_per_request
+            .entry(log.attributes["request_id"].to_string())
+            .or_default() += 1;
+    }
+    assert!(lines_per_request.values().all(|count| *count == 3));
+    assert!(
+        (400..600).contains(&lines_per_request.len()),
+        "kept {} requests",
+        lines_per_request.len()
+    );
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "slog")]
+mod utils;
+
+use datadog_logs::logger::{DataDogDrain, DataDogLog, DataDogLogger};
+use flume::unbounded;
+use serde_json::json;
+use slog::{debug, error, o, Drain, Logger};
+
+#[test]
+fn test_drain_sends_records_with_attributes() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+    let root = Logger::root(
+        DataDogDrain::new(logger).fuse(),
+        o!("app" => "test", "user" => "root"),
+    );
+    let child = root.new(o!("component" => "child", "user" => "child"));
+
+    error!(child, "failed {}", "request"; "status" => 500, "user" => "record", "retry" => false);
+    debug!(root, "debug message");
+
+    std::mem::drop(child);
+    std::mem::drop(root);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!(2, messages.len());
+
+    assert_eq!("failed request", messages[0].message);
+    assert_eq!("err", messages[0].level);
+    assert_eq!(json!("test"), messages[0].attributes["app"]);
+    assert_eq!(json!("child"), messages[0].attributes["component"]);
+    assert_eq!(json!("record"), messages[0].attributes["user"]);
+    assert_eq!(json!(500), messages[0].attributes["status"]);
+    assert_eq!(json!(false), messages[0].attributes["retry"]);
+
+    assert_eq!("debug message", messages[1].message);
+    assert_eq!("debug", messages[1].level);
+    assert_eq!(json!("root"), messages[1].attributes["user"]);
+}
+
+#[test]
+fn test_attributes_are_serialized_as_top_level_fields() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+    let root = Logger::root(DataDogDrain::new(logger).fuse(), o!());
+
+    error!(root, "message"; "request_id" => "abc");
+    std::mem::drop(root);
+
+    let message = receiver.recv().unwrap();
+    let serialized = serde_json::to_value(&message).unwrap();
+    assert_eq!(json!("abc"), serialized["request_id"]);
+    assert_eq!(message, serde_json::from_value(serialized).unwrap());
+}
+
+#[test]
+fn test_attributes_named_like_reserved_fields_are_prefixed() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+    let root = Logger::root(DataDogDrain::new(logger).fuse(), o!("host" => "db-1"));
+
+    error!(root, "message"; "message" => "from attribute", "level" => 3);
+    std::mem::drop(root);
+
+    let serialized = serde_json::to_string(&receiver.recv().unwrap()).unwrap();
+    assert_eq!(1, serialized.matches("\"message\":").count());
+    assert_eq!(1, serialized.matches("\"host\":").count());
+    assert_eq!(1, serialized.matches("\"level\":").count());
+
+    let serialized: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(json!("message"), serialized["message"]);
+    assert_eq!(json!("err"), serialized["level"]);
+    assert_eq!(json!("from attribute"), serialized["attr.message"]);
+    assert_eq!(json!("db-1"), serialized["attr.host"]);
+    assert_eq!(json!(3), serialized["attr.level"]);
+}
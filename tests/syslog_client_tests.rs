@@ -0,0 +1,177 @@
+use datadog_logs::{
+    client::{DataDogClient, SyslogDataDogClient},
+    config::{DataDogConfig, DataDogSyslogConfig, DataDogSyslogFacility},
+    logger::{DataDogLog, DataDogLogLevel},
+};
+use serde_json::json;
+use std::io::Read;
+use std::net::{TcpListener, UdpSocket};
+
+fn client(url: String, facility: DataDogSyslogFacility) -> SyslogDataDogClient {
+    SyslogDataDogClient::new(&DataDogConfig {
+        syslog_config: DataDogSyslogConfig {
+            url,
+            facility,
+            app_name: None,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+fn log(message: &str, level: DataDogLogLevel) -> DataDogLog {
+    let attributes = json!({
+        "logger": {"name": "app::db"},
+        "status": 200,
+        "query": "a \"quoted\" [value]",
+    });
+    DataDogLog {
+        message: message.into(),
+        ddtags: "env:prod,critical".parse().unwrap(),
+        ddsource: "rust".into(),
+        host: "web-1".into(),
+        service: "checkout".into(),
+        env: None,
+        version: None,
+        level: level.to_string(),
+        attributes: attributes.as_object().unwrap().clone(),
+    }
+}
+
+#[test]
+fn test_udp_client_sends_rfc5424_messages() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mut client = client(
+        format!("udp://{}", socket.local_addr().unwrap()),
+        DataDogSyslogFacility::Local0,
+    );
+
+    client
+        .send(&[log("query failed", DataDogLogLevel::Error)])
+        .unwrap();
+
+    let mut buffer = [0; 65536];
+    let size = socket.recv(&mut buffer).unwrap();
+    let message = String::from_utf8(buffer[..size].to_vec()).unwrap();
+    let fields: Vec<&str> = message.splitn(7, ' ').collect();
+
+    // local0 (16) * 8 + error (3)
+    assert_eq!("<131>1", fields[0]);
+    assert!(fields[1].ends_with('Z'));
+    assert_eq!("web-1", fields[2]);
+    assert_eq!("checkout", fields[3]);
+    assert_eq!(std::process::id().to_string(), fields[4]);
+    assert_eq!("-", fields[5]);
+    assert_eq!(
+        r#"[tags@32473 env="prod" critical=""][attributes@32473 logger.name="app::db" query="a \"quoted\" [value\]" status="200"] query failed"#,
+        fields[6]
+    );
+}
+
+#[test]
+fn test_tcp_client_frames_messages_with_octet_counting() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = client(
+        format!("tcp://{}", listener.local_addr().unwrap()),
+        DataDogSyslogFacility::default(),
+    );
+
+    let mut without_metadata = log("second", DataDogLogLevel::Debug);
+    without_metadata.ddtags = Default::default();
+    without_metadata.attributes.clear();
+    without_metadata.host = "".into();
+    client
+        .send(&[log("first", DataDogLogLevel::Warning), without_metadata])
+        .unwrap();
+    std::mem::drop(client);
+
+    let (mut stream, _) = listener.accept().unwrap();
+    let mut received = String::new();
+    stream.read_to_string(&mut received).unwrap();
+
+    let mut messages = Vec::new();
+    let mut rest = received.as_str();
+    while let Some((length, tail)) = rest.split_once(' ') {
+        let length: usize = length.parse().unwrap();
+        messages.push(&tail[..length]);
+        rest = &tail[length..];
+    }
+
+    assert_eq!(2, messages.len());
+    // user (1) * 8 + warning (4)
+    assert!(messages[0].starts_with("<12>1 "));
+    assert!(messages[0].ends_with(" first"));
+    // user (1) * 8 + debug (7), empty host and structured data
+    assert!(messages[1].starts_with("<15>1 "));
+    assert!(messages[1].contains(" - checkout "));
+    assert!(messages[1].ends_with(" - - second"));
+}
+
+#[test]
+fn test_invalid_url_is_rejected() {
+    let config = |url: &str| DataDogConfig {
+        syslog_config: DataDogSyslogConfig {
+            url: url.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(SyslogDataDogClient::new(&config("udp://localhost")).is_err());
+    assert!(SyslogDataDogClient::new(&config("http://localhost:514")).is_err());
+    assert_eq!(
+        cfg!(feature = "native-tls"),
+        SyslogDataDogClient::new(&config("tls://localhost:6514")).is_ok()
+    );
+}
+
+#[test]
+fn test_udp_client_truncates_message_exceeding_datagram() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mut client = client(
+        format!("udp://{}", socket.local_addr().unwrap()),
+        DataDogSyslogFacility::default(),
+    );
+
+    client
+        .send(&[
+            log(&"a".repeat(70_000), DataDogLogLevel::Error),
+            log("small", DataDogLogLevel::Error),
+        ])
+        .unwrap();
+
+    let mut buffer = [0; 65536];
+    let size = socket.recv(&mut buffer).unwrap();
+    assert_eq!(65_507, size);
+    assert!(buffer[..size].ends_with(b"aaa..."));
+    let size = socket.recv(&mut buffer).unwrap();
+    assert!(buffer[..size].ends_with(b" small"));
+    let notices = client.take_self_log();
+    assert_eq!(1, notices.len());
+    assert!(notices[0].starts_with("Truncating message of log"));
+}
+
+#[test]
+fn test_udp_client_drops_log_whose_metadata_exceed_datagram() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mut client = client(
+        format!("udp://{}", socket.local_addr().unwrap()),
+        DataDogSyslogFacility::default(),
+    );
+
+    let mut large = log("large", DataDogLogLevel::Error);
+    large
+        .attributes
+        .insert("payload".into(), "b".repeat(70_000).into());
+    client
+        .send(&[large, log("small", DataDogLogLevel::Error)])
+        .unwrap();
+
+    let mut buffer = [0; 65536];
+    let size = socket.recv(&mut buffer).unwrap();
+    assert!(buffer[..size].ends_with(b" small"));
+    let notices = client.take_self_log();
+    assert_eq!(1, notices.len());
+    assert!(notices[0].starts_with("Dropping log of"));
+}
@@ -0,0 +1,96 @@
+mod utils;
+
+use datadog_logs::{
+    config::DataDogConfig,
+    logger::{DataDogLog, DataDogLogLevel, DataDogLogger, Tags},
+};
+use flume::unbounded;
+
+#[test]
+fn test_tags_are_normalized_and_deduplicated() {
+    let mut tags = Tags::from_list(["Env:Prod", "team:core  payments!", "env:prod"]).unwrap();
+    tags.insert("region", "eu-west-1").unwrap();
+    tags.push(format!("long:{}", "a".repeat(300))).unwrap();
+
+    let values: Vec<&str> = tags.iter().collect();
+    assert_eq!("env:prod", values[0]);
+    assert_eq!("team:core_payments", values[1]);
+    assert_eq!("region:eu-west-1", values[2]);
+    assert_eq!(200, values[3].len());
+    assert_eq!(4, tags.len());
+    assert_eq!(Some("prod"), tags.get("env"));
+}
+
+#[test]
+fn test_replaced_tag_takes_place_of_tags_with_its_key() {
+    let mut tags = Tags::from_list(["env:staging", "team:core", "ENV:qa"]).unwrap();
+    tags.replace("Env", "Prod").unwrap();
+    tags.replace("version", "1.0").unwrap();
+
+    assert_eq!("env:prod,team:core,version:1.0", tags.to_string());
+}
+
+#[test]
+fn test_invalid_tags_are_rejected() {
+    assert!(Tags::new().push("1st").is_err());
+    assert!(Tags::new().push(":value").is_err());
+    assert!(Tags::new().push("!!!").is_err());
+    assert!("env:prod,,".parse::<Tags>().is_ok());
+}
+
+#[test]
+fn test_tags_are_configurable_as_string_list_or_map() {
+    let from_string: DataDogConfig =
+        serde_json::from_str(r#"{"apikey": "", "tags": "env:prod,team:core"}"#).unwrap();
+    let from_list: DataDogConfig =
+        serde_yaml::from_str("apikey: ''\ntags:\n  - env:prod\n  - team:core\n").unwrap();
+    let from_map: DataDogConfig =
+        serde_yaml::from_str("apikey: ''\ntags:\n  env: prod\n  team: core\n").unwrap();
+    let missing: DataDogConfig = serde_json::from_str(r#"{"apikey": ""}"#).unwrap();
+
+    assert_eq!("env:prod,team:core", from_string.tags.to_string());
+    assert_eq!(from_string.tags, from_list.tags);
+    assert_eq!(from_string.tags, from_map.tags);
+    assert!(missing.tags.is_empty());
+
+    let invalid: Result<DataDogConfig, _> = serde_yaml::from_str("apikey: ''\ntags:\n  - 1st\n");
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn test_message_tags_are_added_to_configured_tags() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .tags("env:prod".parse().unwrap())
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    let tags = Tags::from_pairs([("order", "1234"), ("env", "prod")]).unwrap();
+    logger.log_with("shipped", DataDogLogLevel::Informational, &tags);
+    logger.log("started", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!("env:prod,order:1234", messages[0].ddtags.to_string());
+    assert_eq!("env:prod", messages[1].ddtags.to_string());
+
+    let json = serde_json::to_value(&messages[0]).unwrap();
+    assert_eq!("env:prod,order:1234", json["ddtags"]);
+}
+
+#[test]
+fn test_empty_tags_are_not_serialized() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log("started", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    let json = serde_json::to_value(&messages[0]).unwrap();
+    assert!(json.get("ddtags").is_none());
+}
@@ -0,0 +1,50 @@
+use datadog_logs::{
+    client::{DataDogClient, TcpDataDogClient},
+    config::{DataDogConfig, DataDogTcpConfig},
+    logger::DataDogLog,
+};
+use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
+
+#[test]
+fn test_tcp_client_sends_lines_prefixed_with_api_key() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let config = DataDogConfig {
+        apikey: "secret".into(),
+        tcp_config: DataDogTcpConfig {
+            use_tls: false,
+            domain: "127.0.0.1".into(),
+            non_tls_port: listener.local_addr().unwrap().port() as usize,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut client = TcpDataDogClient::new(&config).unwrap();
+
+    let log = DataDogLog {
+        message: "message".into(),
+        ddtags: Default::default(),
+        ddsource: "rust".into(),
+        host: "host".into(),
+        service: "service".into(),
+        env: None,
+        version: None,
+        level: "info".into(),
+        attributes: Default::default(),
+    };
+    client.send(&[log.clone(), log.clone()]).unwrap();
+
+    let (stream, _) = listener.accept().unwrap();
+    let lines: Vec<String> = BufReader::new(stream)
+        .lines()
+        .take(2)
+        .map(|line| line.unwrap())
+        .collect();
+
+    assert_eq!(2, lines.len());
+    for line in lines {
+        let (apikey, json) = line.split_at(line.find(' ').unwrap());
+        assert_eq!("secret", apikey);
+        assert_eq!(log, serde_json::from_str(json.trim()).unwrap());
+    }
+}
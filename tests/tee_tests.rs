@@ -0,0 +1,107 @@
+mod utils;
+
+use datadog_logs::logger::{DataDogLog, DataDogLogLevel, DataDogLogger, LocalFormat, LocalSink};
+use flume::unbounded;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_text_sink_is_written_synchronously() {
+    let buffer = SharedBuffer::default();
+    let (sender, receiver) = unbounded();
+    let mut client = utils::DataDogClientStub::new(sender);
+    client.should_error = true;
+    let logger = DataDogLogger::builder()
+        .tee(LocalSink::writer(buffer.clone(), LocalFormat::Text))
+        .client(client)
+        .build_blocking()
+        .unwrap();
+
+    logger.log("first", DataDogLogLevel::Error);
+    logger.log("second", DataDogLogLevel::Informational);
+
+    // written before logger thread had a chance to send anything
+    let lines = buffer.lines();
+    assert_eq!(2, lines.len());
+    assert!(lines[0].ends_with(" ERR     first"), "{}", lines[0]);
+    assert!(lines[1].ends_with(" INFO    second"), "{}", lines[1]);
+    // timestamp like 2021-01-01T00:00:00.000Z
+    let timestamp = lines[0].split(' ').next().unwrap();
+    assert_eq!(24, timestamp.len());
+    assert!(timestamp.ends_with('Z'));
+
+    std::mem::drop(logger);
+    assert_eq!(0, receiver.iter().count());
+}
+
+#[test]
+fn test_json_sink_writes_datadog_logs() {
+    let buffer = SharedBuffer::default();
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .service("tee-service")
+        .tee(LocalSink::writer(buffer.clone(), LocalFormat::Json))
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log("message", DataDogLogLevel::Warning);
+    std::mem::drop(logger);
+
+    let sent: Vec<DataDogLog> = receiver.iter().collect();
+    let written: Vec<DataDogLog> = buffer
+        .lines()
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(sent, written);
+    assert_eq!("tee-service", &*written[0].service);
+}
+
+#[test]
+fn test_sink_receives_redacted_logs() {
+    let buffer = SharedBuffer::default();
+    let (sender, _receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .apikey("0123456789abcdef")
+        .redact_builtin(true)
+        .tee(LocalSink::writer(buffer.clone(), LocalFormat::Text))
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log(
+        "key 0123456789abcdef of john.doe@example.com",
+        DataDogLogLevel::Error,
+    );
+
+    let lines = buffer.lines();
+    assert!(
+        lines[0].ends_with(" key [REDACTED_API_KEY] of [REDACTED_EMAIL]"),
+        "{}",
+        lines[0]
+    );
+}
@@ -0,0 +1,405 @@
+#![cfg(any(
+    feature = "native-tls",
+    feature = "attohttpc",
+    feature = "ureq",
+    feature = "reqwest",
+    feature = "hyper"
+))]
+#[cfg(any(feature = "reqwest", feature = "hyper"))]
+use datadog_logs::client::AsyncDataDogClient;
+#[cfg(any(feature = "attohttpc", feature = "ureq", feature = "native-tls"))]
+use datadog_logs::client::DataDogClient;
+use datadog_logs::{
+    client::{HttpDataDogClient, TcpDataDogClient},
+    config::{DataDogConfig, DataDogHttpConfig, DataDogTcpConfig, DataDogTlsConfig},
+    error::DataDogLoggerError,
+    logger::DataDogLog,
+};
+use flume::{unbounded, Receiver, Sender};
+use rustls::{
+    crypto::ring,
+    pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer},
+    server::WebPkiClientVerifier,
+    RootCertStore, ServerConfig, ServerConnection, StreamOwned,
+};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls");
+/// SHA-256 of public key of `server.pem`
+#[cfg(feature = "pinning")]
+const SERVER_PIN: &str = "tZvhWgrEVkFb3vJzksMoXpghb97lSEEmKFTET4OB6H8=";
+
+type Connection = StreamOwned<ServerConnection, TcpStream>;
+
+fn fixture(name: &str) -> String {
+    format!("{}/{}", FIXTURES, name)
+}
+
+fn certificates(name: &str) -> Vec<CertificateDer<'static>> {
+    rustls_pemfile::certs(&mut BufReader::new(File::open(fixture(name)).unwrap()))
+        .unwrap()
+        .into_iter()
+        .map(CertificateDer::from)
+        .collect()
+}
+
+/// Starts TLS server with certificate signed by the test CA, optionally requiring client certificate signed by it too.
+/// Every connection is handled on a new thread and whatever handler returns is recorded.
+fn start_server(
+    client_auth: bool,
+    handler: fn(&mut Connection) -> Option<String>,
+) -> (u16, Receiver<String>) {
+    let provider = Arc::new(ring::default_provider());
+    let mut roots = RootCertStore::empty();
+    for certificate in certificates("ca.pem") {
+        roots.add(certificate).unwrap();
+    }
+    let key = rustls_pemfile::pkcs8_private_keys(&mut BufReader::new(
+        File::open(fixture("server.key")).unwrap(),
+    ))
+    .unwrap()
+    .remove(0);
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .unwrap();
+    let builder = if client_auth {
+        builder.with_client_cert_verifier(
+            WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .unwrap(),
+        )
+    } else {
+        builder.with_no_client_auth()
+    };
+    let config = builder
+        .with_single_cert(
+            certificates("server.pem"),
+            PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key)),
+        )
+        .unwrap();
+    let config = Arc::new(config);
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sender, receiver): (Sender<String>, _) = unbounded();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let config = config.clone();
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let mut connection =
+                    StreamOwned::new(ServerConnection::new(config).unwrap(), stream);
+                if let Some(received) = handler(&mut connection) {
+                    sender.send(received).unwrap_or_default();
+                }
+            });
+        }
+    });
+
+    (port, receiver)
+}
+
+/// Reads HTTP request and responds with 200, recording its body
+#[cfg(any(
+    feature = "attohttpc",
+    feature = "ureq",
+    feature = "reqwest",
+    feature = "hyper"
+))]
+fn http_handler(connection: &mut Connection) -> Option<String> {
+    use std::io::{Read, Write};
+
+    let mut reader = BufReader::new(&mut *connection);
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if key.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok()?;
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    connection
+        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+        .ok()?;
+    connection.conn.send_close_notify();
+    connection.flush().ok()?;
+    String::from_utf8(body).ok()
+}
+
+/// Records first line sent over connection
+#[cfg(feature = "native-tls")]
+fn line_handler(connection: &mut Connection) -> Option<String> {
+    let mut line = String::new();
+    BufReader::new(connection).read_line(&mut line).ok()?;
+    Some(line)
+}
+
+fn tls_config() -> DataDogTlsConfig {
+    DataDogTlsConfig {
+        root_certificates: vec![fixture("ca.pem")],
+        disable_system_roots: true,
+        client_certificate: Some(fixture("client.pem")),
+        client_key: Some(fixture("client.key")),
+        pinned_spki_sha256: Vec::new(),
+    }
+}
+
+fn http_client(port: u16, tls: DataDogTlsConfig) -> Result<HttpDataDogClient, DataDogLoggerError> {
+    HttpDataDogClient::new(&DataDogConfig {
+        apikey: "secret".into(),
+        http_config: DataDogHttpConfig {
+            url: format!("https://localhost:{}/v1/input", port),
+            tls,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn log(message: &str) -> DataDogLog {
+    DataDogLog {
+        message: message.into(),
+        ddtags: Default::default(),
+        ddsource: "rust".into(),
+        host: "host".into(),
+        service: "service".into(),
+        env: None,
+        version: None,
+        level: "info".into(),
+        attributes: Default::default(),
+    }
+}
+
+#[cfg(any(feature = "attohttpc", feature = "ureq"))]
+#[test]
+fn test_http_client_trusts_private_ca() {
+    let (port, received) = start_server(false, http_handler);
+    let mut client = http_client(
+        port,
+        DataDogTlsConfig {
+            root_certificates: vec![fixture("ca.pem")],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    client.send(&[log("over private CA")]).unwrap();
+
+    let body = received.recv_timeout(Duration::from_secs(5)).unwrap();
+    let logs: Vec<DataDogLog> = serde_json::from_str(&body).unwrap();
+    assert_eq!(vec![log("over private CA")], logs);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn test_http_client_trusts_private_ca_and_presents_client_certificate() {
+    let (port, received) = start_server(true, http_handler);
+    let mut client = http_client(port, tls_config()).unwrap();
+
+    client.send(&[log("over mutual TLS")]).unwrap();
+
+    let body = received.recv_timeout(Duration::from_secs(5)).unwrap();
+    let logs: Vec<DataDogLog> = serde_json::from_str(&body).unwrap();
+    assert_eq!(vec![log("over mutual TLS")], logs);
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn test_http_client_without_client_certificate_is_rejected() {
+    let (port, received) = start_server(true, http_handler);
+    let mut client = http_client(
+        port,
+        DataDogTlsConfig {
+            client_certificate: None,
+            client_key: None,
+            ..tls_config()
+        },
+    )
+    .unwrap();
+
+    assert!(client.send(&[log("anonymous")]).is_err());
+    assert!(received.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn test_http_client_sends_only_to_pinned_public_key() {
+    let (port, received) = start_server(true, http_handler);
+
+    let mut pinned = http_client(
+        port,
+        DataDogTlsConfig {
+            pinned_spki_sha256: vec![SERVER_PIN.into()],
+            ..tls_config()
+        },
+    )
+    .unwrap();
+    pinned.send(&[log("pinned")]).unwrap();
+    assert!(received
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap()
+        .contains("pinned"));
+
+    let mut mismatched = http_client(
+        port,
+        DataDogTlsConfig {
+            pinned_spki_sha256: vec!["AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".into()],
+            ..tls_config()
+        },
+    )
+    .unwrap();
+    let error = mismatched.send(&[log("mismatched")]).unwrap_err();
+    assert!(error.to_string().contains("does not match any pin"));
+    assert!(received.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[cfg(not(any(feature = "ureq", feature = "nonblocking-core")))]
+#[test]
+fn test_http_client_without_transport_supporting_tls_config_is_rejected() {
+    match http_client(443, tls_config()) {
+        Err(DataDogLoggerError::ConfigError(e)) => assert!(e.contains("ureq")),
+        other => panic!("Expected configuration error, got {:?}", other),
+    }
+}
+
+#[cfg(any(feature = "reqwest", feature = "hyper"))]
+#[tokio::test]
+async fn test_nonblocking_http_client_uses_tls_config() {
+    let (port, received) = start_server(true, http_handler);
+
+    let mut pinned = http_client(
+        port,
+        DataDogTlsConfig {
+            pinned_spki_sha256: vec![SERVER_PIN.into()],
+            ..tls_config()
+        },
+    )
+    .unwrap();
+    pinned.send_async(&[log("pinned")]).await.unwrap();
+    assert!(received
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap()
+        .contains("pinned"));
+
+    for tls in [
+        DataDogTlsConfig {
+            client_certificate: None,
+            client_key: None,
+            ..tls_config()
+        },
+        DataDogTlsConfig {
+            pinned_spki_sha256: vec!["AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".into()],
+            ..tls_config()
+        },
+    ] {
+        let mut rejected = http_client(port, tls).unwrap();
+        assert!(rejected.send_async(&[log("rejected")]).await.is_err());
+    }
+    assert!(received.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[cfg(feature = "hyper")]
+#[tokio::test]
+async fn test_hyper_transport_uses_tls_config() {
+    let (port, received) = start_server(true, http_handler);
+    let config = DataDogConfig {
+        apikey: "secret".into(),
+        http_config: DataDogHttpConfig {
+            url: format!("https://localhost:{}/v1/input", port),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let transport = datadog_logs::client::HyperTransport::with_tls(&DataDogTlsConfig {
+        pinned_spki_sha256: vec![SERVER_PIN.into()],
+        ..tls_config()
+    })
+    .unwrap();
+    let mut client = HttpDataDogClient::with_transport(&config, transport).unwrap();
+
+    client.send_async(&[log("over hyper")]).await.unwrap();
+
+    let body = received.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(body.contains("over hyper"));
+}
+
+#[cfg(feature = "native-tls")]
+#[test]
+fn test_tcp_client_uses_tls_config() {
+    let (port, received) = start_server(true, line_handler);
+    let mut client = TcpDataDogClient::new(&DataDogConfig {
+        apikey: "secret".into(),
+        tcp_config: DataDogTcpConfig {
+            use_tls: true,
+            domain: "localhost".into(),
+            tls_port: port as usize,
+            tls: DataDogTlsConfig {
+                pinned_spki_sha256: vec![SERVER_PIN.into()],
+                ..tls_config()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap();
+
+    client.send(&[log("over TCP")]).unwrap();
+
+    let line = received.recv_timeout(Duration::from_secs(5)).unwrap();
+    let (apikey, json) = line.split_at(line.find(' ').unwrap());
+    assert_eq!("secret", apikey);
+    assert_eq!(log("over TCP"), serde_json::from_str(json.trim()).unwrap());
+}
+
+#[cfg(not(feature = "native-tls"))]
+#[test]
+fn test_tcp_client_without_native_tls_is_rejected() {
+    let config = DataDogConfig {
+        tcp_config: DataDogTcpConfig {
+            use_tls: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    match TcpDataDogClient::new(&config) {
+        Err(DataDogLoggerError::ConfigError(e)) => assert!(e.contains("native-tls")),
+        other => panic!("Expected configuration error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_tls_config_is_rejected() {
+    let without_key = DataDogTlsConfig {
+        client_key: None,
+        ..tls_config()
+    };
+    let invalid_pin = DataDogTlsConfig {
+        pinned_spki_sha256: vec!["not a pin".into()],
+        ..tls_config()
+    };
+    let missing_file = DataDogTlsConfig {
+        root_certificates: vec![fixture("missing.pem")],
+        ..tls_config()
+    };
+
+    for tls in [without_key, invalid_pin, missing_file] {
+        match http_client(443, tls) {
+            Err(DataDogLoggerError::ConfigError(_)) => {}
+            other => panic!("Expected configuration error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+mod utils;
+
+#[cfg(feature = "nonblocking-core")]
+use datadog_logs::client::{AsyncDataDogClient, AsyncHttpTransport};
+use datadog_logs::{
+    client::{
+        DataDogClient, DefaultHttpTransport, HttpDataDogClient, HttpRequest, HttpResponse,
+        HttpTransport,
+    },
+    config::DataDogConfig,
+    error::DataDogLoggerError,
+    logger::DataDogLog,
+};
+use flume::{unbounded, Sender};
+use std::time::Duration;
+use utils::http::HttpStandIn;
+
+/// Transport answering every request with given status
+struct RecordingTransport {
+    status: u16,
+    requests: Sender<HttpRequest>,
+}
+
+impl HttpTransport for RecordingTransport {
+    fn send(&mut self, request: &HttpRequest) -> Result<HttpResponse, DataDogLoggerError> {
+        self.requests.send(request.clone()).unwrap_or_default();
+        Ok(HttpResponse {
+            status: self.status,
+            body: "response body".into(),
+        })
+    }
+}
+
+fn config(url: &str) -> DataDogConfig {
+    let mut config = DataDogConfig {
+        apikey: "transport-key".into(),
+        ..Default::default()
+    };
+    config.http_config.url = url.into();
+    config
+}
+
+fn log(message: &str) -> DataDogLog {
+    DataDogLog {
+        message: message.into(),
+        ddtags: Default::default(),
+        ddsource: "rust".into(),
+        host: "host".into(),
+        service: "service".into(),
+        env: None,
+        version: None,
+        level: "info".into(),
+        attributes: Default::default(),
+    }
+}
+
+fn assert_received(stand_in: &HttpStandIn, message: &str) {
+    let request = stand_in
+        .requests
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap();
+    assert_eq!("/v1/input", request.path);
+    assert_eq!(Some("application/json"), request.header("Content-Type"));
+    assert_eq!(Some("transport-key"), request.header("DD-API-KEY"));
+    let logs: Vec<DataDogLog> = serde_json::from_str(&request.body).unwrap();
+    assert_eq!(vec![log(message)], logs);
+}
+
+fn check_blocking<T: HttpTransport>(transport: T) {
+    let stand_in = HttpStandIn::start();
+    let mut client = HttpDataDogClient::with_transport(&config(&stand_in.url), transport).unwrap();
+
+    client.send(&[log("blocking")]).unwrap();
+    assert_received(&stand_in, "blocking");
+
+    stand_in.set_status(503);
+    let error = client.send(&[log("rejected")]).unwrap_err();
+    assert!(error.to_string().contains("Status code : 503"));
+}
+
+#[cfg(feature = "nonblocking-core")]
+async fn check_nonblocking<T: AsyncHttpTransport>(transport: T) {
+    let stand_in = HttpStandIn::start();
+    let mut client = HttpDataDogClient::with_transport(&config(&stand_in.url), transport).unwrap();
+
+    client.send_async(&[log("nonblocking")]).await.unwrap();
+    assert_received(&stand_in, "nonblocking");
+
+    stand_in.set_status(503);
+    let error = client.send_async(&[log("rejected")]).await.unwrap_err();
+    assert!(error.to_string().contains("Status code : 503"));
+}
+
+#[test]
+fn test_client_builds_requests_and_classifies_responses_for_any_transport() {
+    let (sender, requests) = unbounded();
+    let transport = RecordingTransport {
+        status: 202,
+        requests: sender.clone(),
+    };
+    let mut client =
+        HttpDataDogClient::with_transport(&config("https://intake.example/v1/input"), transport)
+            .unwrap();
+    client.send(&[log("accepted")]).unwrap();
+
+    let request = requests.try_recv().unwrap();
+    assert_eq!("https://intake.example/v1/input", request.url.as_str());
+    assert_eq!(
+        vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("DD-API-KEY".to_string(), "transport-key".to_string()),
+            (
+                "User-Agent".to_string(),
+                concat!("datadog-logs/", env!("CARGO_PKG_VERSION")).to_string()
+            )
+        ],
+        request.headers
+    );
+    assert_eq!(
+        serde_json::to_vec(&[log("accepted")]).unwrap(),
+        request.body
+    );
+    assert!(!format!("{:?}", request).contains("transport-key"));
+
+    let transport = RecordingTransport {
+        status: 403,
+        requests: sender,
+    };
+    let mut client =
+        HttpDataDogClient::with_transport(&config("https://intake.example/v1/input"), transport)
+            .unwrap();
+    let error = client.send(&[log("forbidden")]).unwrap_err();
+    assert_eq!(
+        "Datadog response does not indicate success. Status code : 403, Body : response body",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_default_transport() {
+    let config = DataDogConfig::default();
+    check_blocking(DefaultHttpTransport::new(&config.http_config).unwrap());
+}
+
+#[cfg(feature = "nonblocking-core")]
+#[tokio::test]
+async fn test_default_transport_nonblocking() {
+    let config = DataDogConfig::default();
+    check_nonblocking(DefaultHttpTransport::new(&config.http_config).unwrap()).await;
+}
+
+#[cfg(feature = "nonblocking-core")]
+#[tokio::test]
+async fn test_default_transport_does_not_block_task_waiting_for_response() {
+    let stand_in = HttpStandIn::start();
+    stand_in.set_delay(Duration::from_secs(1));
+    let transport = DefaultHttpTransport::new(&DataDogConfig::default().http_config).unwrap();
+    let mut client = HttpDataDogClient::with_transport(&config(&stand_in.url), transport).unwrap();
+
+    let logs = [log("delayed")];
+    let mut sending = client.send_async(&logs);
+    assert!(futures::poll!(&mut sending).is_pending());
+    sending.await.unwrap();
+    assert_received(&stand_in, "delayed");
+}
+
+#[cfg(feature = "attohttpc")]
+#[test]
+fn test_attohttpc_transport() {
+    check_blocking(datadog_logs::client::AttohttpcTransport::new());
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn test_ureq_transport() {
+    check_blocking(datadog_logs::client::UreqTransport::new());
+}
+
+#[cfg(feature = "reqwest")]
+#[tokio::test]
+async fn test_reqwest_transport() {
+    check_nonblocking(datadog_logs::client::ReqwestTransport::new()).await;
+}
+
+#[cfg(feature = "hyper")]
+#[tokio::test]
+async fn test_hyper_transport() {
+    check_nonblocking(datadog_logs::client::HyperTransport::new()).await;
+}
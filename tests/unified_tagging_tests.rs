@@ -0,0 +1,89 @@
+mod utils;
+
+use datadog_logs::logger::{DataDogLog, DataDogLogLevel, DataDogLogger};
+use flume::unbounded;
+
+#[test]
+fn test_env_and_version_are_sent_as_attributes_and_tags() {
+    let (sender, receiver) = unbounded();
+    let (from_environment, from_config) = utils::env::with_vars(
+        &[("DD_ENV", Some("staging")), ("DD_VERSION", Some("2.0.0"))],
+        || {
+            let from_environment = DataDogLogger::builder()
+                .tags("team:core".parse().unwrap())
+                .client(utils::DataDogClientStub::new(sender.clone()))
+                .build_blocking()
+                .unwrap();
+            let from_config = DataDogLogger::builder()
+                .env("prod")
+                .version("1.2.3")
+                .client(utils::DataDogClientStub::new(sender.clone()))
+                .build_blocking()
+                .unwrap();
+            (from_environment, from_config)
+        },
+    );
+    let (from_macro, unset) =
+        utils::env::with_vars(&[("DD_ENV", None), ("DD_VERSION", None)], || {
+            let from_macro = datadog_logs::builder!()
+                .client(utils::DataDogClientStub::new(sender.clone()))
+                .build_blocking()
+                .unwrap();
+            let unset = DataDogLogger::builder()
+                .client(utils::DataDogClientStub::new(sender))
+                .build_blocking()
+                .unwrap();
+            (from_macro, unset)
+        });
+
+    assert_eq!(Some("staging"), from_environment.config().env.as_deref());
+    assert_eq!(Some("2.0.0"), from_environment.config().version.as_deref());
+
+    for logger in [from_environment, from_config, from_macro, unset] {
+        logger.log("message", DataDogLogLevel::Informational);
+    }
+
+    let messages: Vec<DataDogLog> = receiver.iter().collect();
+    assert_eq!(4, messages.len());
+
+    assert_eq!(Some("staging".into()), messages[0].env);
+    assert_eq!(Some("2.0.0".into()), messages[0].version);
+    assert_eq!(
+        "team:core,env:staging,version:2.0.0",
+        messages[0].ddtags.to_string()
+    );
+
+    let json = serde_json::to_value(&messages[1]).unwrap();
+    assert_eq!("prod", json["env"]);
+    assert_eq!("1.2.3", json["version"]);
+    assert_eq!("env:prod,version:1.2.3", json["ddtags"]);
+
+    assert_eq!(None, messages[2].env);
+    assert_eq!(Some(env!("CARGO_PKG_VERSION").into()), messages[2].version);
+
+    let json = serde_json::to_value(&messages[3]).unwrap();
+    assert!(json.get("env").is_none());
+    assert!(json.get("version").is_none());
+    assert!(json.get("ddtags").is_none());
+}
+
+#[test]
+fn test_configured_env_and_version_replace_tags_with_their_keys() {
+    let (sender, receiver) = unbounded();
+    let logger = DataDogLogger::builder()
+        .tags("env:staging,team:core,version:0.9".parse().unwrap())
+        .env("prod")
+        .version("1.2.3")
+        .client(utils::DataDogClientStub::new(sender))
+        .build_blocking()
+        .unwrap();
+
+    logger.log("message", DataDogLogLevel::Informational);
+    std::mem::drop(logger);
+
+    let message = receiver.recv().unwrap();
+    assert_eq!(
+        "env:prod,team:core,version:1.2.3",
+        message.ddtags.to_string()
+    );
+}
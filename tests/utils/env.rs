@@ -0,0 +1,38 @@
+use std::env;
+use std::sync::Mutex;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Runs closure with environment variables set or removed, restoring them afterwards.
+///
+/// Calls are serialized, so tests of one binary reading environment in the closure do not race.
+pub fn with_vars<R>(vars: &[(&str, Option<&str>)], f: impl FnOnce() -> R) -> R {
+    let _lock = LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let _restore = Restore(
+        vars.iter()
+            .map(|(name, _)| (name.to_string(), env::var(name).ok()))
+            .collect(),
+    );
+    for (name, value) in vars {
+        set(name, *value);
+    }
+    f()
+}
+
+/// Restores previous values when dropped, also if the closure panics
+struct Restore(Vec<(String, Option<String>)>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        for (name, value) in self.0.iter() {
+            set(name, value.as_deref());
+        }
+    }
+}
+
+fn set(name: &str, value: Option<&str>) {
+    match value {
+        Some(value) => env::set_var(name, value),
+        None => env::remove_var(name),
+    }
+}
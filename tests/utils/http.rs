@@ -0,0 +1,119 @@
+use flume::{unbounded, Receiver, Sender};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{
+    atomic::{AtomicU16, AtomicU64, Ordering},
+    Arc,
+};
+use std::thread;
+use std::time::Duration;
+
+/// Request received by `HttpStandIn`
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Local HTTP server standing in for DataDog intake
+pub struct HttpStandIn {
+    pub url: String,
+    pub requests: Receiver<HttpRequest>,
+    status: Arc<AtomicU16>,
+    delay: Arc<AtomicU64>,
+}
+
+impl HttpStandIn {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1/input", listener.local_addr().unwrap());
+        let status = Arc::new(AtomicU16::new(200));
+        let (sender, receiver) = unbounded();
+
+        let delay = Arc::new(AtomicU64::new(0));
+        let thread_status = status.clone();
+        let thread_delay = delay.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let delay = Duration::from_millis(thread_delay.load(Ordering::SeqCst));
+                handle(stream, thread_status.load(Ordering::SeqCst), delay, &sender);
+            }
+        });
+
+        HttpStandIn {
+            url,
+            requests: receiver,
+            status,
+            delay,
+        }
+    }
+
+    /// Sets status code returned for subsequent requests
+    pub fn set_status(&self, status: u16) {
+        self.status.store(status, Ordering::SeqCst);
+    }
+
+    /// Sets time waited before responding to subsequent requests
+    pub fn set_delay(&self, delay: Duration) {
+        self.delay.store(delay.as_millis() as u64, Ordering::SeqCst);
+    }
+}
+
+fn handle(
+    mut stream: TcpStream,
+    status: u16,
+    delay: Duration,
+    sender: &Sender<HttpRequest>,
+) -> Option<()> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let path = request_line.split_whitespace().nth(1)?.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (key, value) = line.split_at(line.find(':')?);
+        headers.push((key.to_string(), value[1..].trim().to_string()));
+    }
+
+    let length = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    // request is recorded before responding, so it is visible once client gets the response
+    let request = HttpRequest {
+        path,
+        headers,
+        body: String::from_utf8(body).ok()?,
+    };
+    sender.send(request).unwrap_or_default();
+
+    thread::sleep(delay);
+    write!(
+        stream,
+        "HTTP/1.1 {} Stand-in\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )
+    .ok()
+}
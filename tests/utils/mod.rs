@@ -1,8 +1,12 @@
+#![allow(dead_code)]
+pub mod env;
+pub mod http;
+
+#[cfg(feature = "nonblocking-core")]
 use async_trait::async_trait;
-use datadog_logs::{
-    client::{AsyncDataDogClient, DataDogClient},
-    error::DataDogLoggerError,
-};
+#[cfg(feature = "nonblocking-core")]
+use datadog_logs::client::AsyncDataDogClient;
+use datadog_logs::{client::DataDogClient, error::DataDogLoggerError};
 use flume::Sender;
 
 pub struct DataDogClientStub {
@@ -37,6 +41,7 @@ impl DataDogClient for DataDogClientStub {
     }
 }
 
+#[cfg(feature = "nonblocking-core")]
 #[async_trait]
 impl AsyncDataDogClient for DataDogClientStub {
     async fn send_async(